@@ -1,19 +1,40 @@
 use crate::{
     commands::helpers::get_game_response_from_store,
     defaults::games::{DefaultGameInfo, SUPPORTED_GAMES},
-    dto::games::{GameRequestDto, GameResponseDto},
+    diagnostics,
+    dto::{
+        games::{GameRequestDto, GameResponseDto},
+        saves::{SaveBackupDto, SaveCompatibilityDto, SaveQueryDto, SaveResponseDto},
+        sessions::PlayHistoryDto,
+    },
+    events::AppEvent,
     join_path,
-    launchers::{self, GameManager},
-    mods,
-    state::AppState,
-    stores::games::{GameStore, Profile, Store},
+    launchers::{
+        self, GameManager,
+        hooks::{HookContext, LaunchHooks},
+        monitor::GameProcess,
+    },
+    mods::{
+        self, scan,
+        validation::{self, DependencyPolicy},
+    },
+    saves::{backups, header::SaveHeader, links},
+    state::{ActiveSession, AppState},
+    stores::{
+        games::{GameStore, Profile, SessionOutcome, Store},
+        settings::SettingsStore,
+    },
     supported_games::SupportedGames,
     utils::ErrorCode,
 };
 
 use std::collections::HashMap;
 
-use std::path::PathBuf;
+use std::ffi::OsStr;
+
+use std::path::{Path, PathBuf};
+
+use tauri::Emitter;
 
 #[tauri::command]
 
@@ -33,39 +54,148 @@ pub fn get_supported_games() -> serde_json::Value {
 
 #[tauri::command]
 
-pub fn get_saves(game_id: SupportedGames) -> Result<Vec<String>, ErrorCode> {
-    let game_info = DefaultGameInfo::find_by_id(game_id).ok_or(ErrorCode::NotFound)?;
+pub async fn get_saves(
+    app_handle: tauri::AppHandle,
+
+    game_id: SupportedGames,
 
-    let saves_path = PathBuf::from(&game_info.saves_path);
+    query: Option<SaveQueryDto>,
+) -> Result<Vec<SaveResponseDto>, ErrorCode> {
+    let game = GameStore::from_entries(GameStore::get_store(&app_handle, game_id)?.entries())?;
 
-    if !saves_path.exists() {
-        log::warn!(
-            "get_saves: path does not exist for game {:?}: {}",
-            game_id,
-            saves_path.display()
-        );
+    let Some(saves_path) = game.saves_path.filter(|path| path.exists()) else {
+        log::warn!("get_saves: no saves folder found for game {:?}", game_id);
 
         return Err(ErrorCode::NotFound);
-    }
+    };
 
-    let entries = std::fs::read_dir(&saves_path)
-        .map_err(|e| {
-            log::warn!("Failed to read saves directory {:?}: {:?}", saves_path, e);
+    let saves = SaveResponseDto::list(&saves_path, &game.save_links);
+
+    Ok(query.unwrap_or_default().apply(saves))
+}
+
+#[tauri::command]
+
+pub async fn delete_save<'a>(
+    app_handle: tauri::AppHandle,
+
+    state: AppState<'a>,
+
+    game_id: SupportedGames,
+
+    save_name: String,
+) -> Result<(), ErrorCode> {
+    log::info!("delete_save: game={:?}, save={}", game_id, save_name);
+
+    ensure_game_not_running(&state).await?;
+
+    GameStore::get(&app_handle, game_id, |game| {
+        let path = save_file_path(game, &save_name)?;
+
+        std::fs::remove_file(&path).map_err(|e| {
+            log::error!("delete_save: failed to remove {}: {:?}", path.display(), e);
 
             ErrorCode::InternalError
-        })?
-        .filter_map(|entry| {
-            entry.ok().and_then(|e| {
-                if e.path().is_file() {
-                    e.file_name().into_string().ok()
-                } else {
-                    None
-                }
-            })
-        })
-        .collect();
+        })?;
 
-    Ok(entries)
+        game.save_links.remove(&save_name);
+
+        Ok(())
+    })
+    .await
+}
+
+#[tauri::command]
+
+pub async fn rename_save<'a>(
+    app_handle: tauri::AppHandle,
+
+    state: AppState<'a>,
+
+    game_id: SupportedGames,
+
+    save_name: String,
+
+    new_name: String,
+) -> Result<SaveResponseDto, ErrorCode> {
+    log::info!(
+        "rename_save: game={:?}, save={}, new_name={}",
+        game_id,
+        save_name,
+        new_name
+    );
+
+    ensure_game_not_running(&state).await?;
+
+    let new_name = new_name.trim();
+
+    let new_name = if new_name.to_lowercase().ends_with(".save") {
+        new_name.to_string()
+    } else {
+        format!("{}.save", new_name)
+    };
+
+    GameStore::get(&app_handle, game_id, |game| {
+        if Path::new(&new_name).file_name() != Some(OsStr::new(&new_name)) {
+            log::warn!("rename_save: invalid save name '{}'", new_name);
+
+            return Err(ErrorCode::BadRequest);
+        }
+
+        let path = save_file_path(game, &save_name)?;
+
+        let target = path.with_file_name(&new_name);
+
+        if target.exists() {
+            return Err(ErrorCode::Conflict);
+        }
+
+        std::fs::rename(&path, &target).map_err(|e| {
+            log::error!("rename_save: failed to rename {}: {:?}", path.display(), e);
+
+            ErrorCode::InternalError
+        })?;
+
+        let link = game.save_links.remove(&save_name);
+
+        if let Some(link) = &link {
+            game.save_links.insert(new_name.clone(), link.clone());
+        }
+
+        Ok(SaveResponseDto::new(target, link.as_ref()))
+    })
+    .await
+}
+
+// Saves are refused while the game runs: it may be loading or writing them.
+async fn ensure_game_not_running(state: &AppState<'_>) -> Result<(), ErrorCode> {
+    if state.lock().await.game_runner.is_some() {
+        log::warn!("Refusing to modify saves while the game is running");
+
+        return Err(ErrorCode::Conflict);
+    }
+
+    Ok(())
+}
+
+// Resolves a save name against the game's saves folder; only plain file names of
+// existing saves are accepted.
+pub(crate) fn save_file_path(game: &GameStore, save_name: &str) -> Result<PathBuf, ErrorCode> {
+    let saves_path = game.saves_path.as_ref().ok_or(ErrorCode::NotFound)?;
+
+    if Path::new(save_name).file_name() != Some(OsStr::new(save_name)) {
+        log::warn!("Invalid save name '{}'", save_name);
+
+        return Err(ErrorCode::BadRequest);
+    }
+
+    let path = saves_path.join(save_name);
+
+    if !path.is_file() || !links::is_save_file(&path) {
+        return Err(ErrorCode::NotFound);
+    }
+
+    Ok(path)
 }
 
 #[tauri::command]
@@ -75,6 +205,10 @@ pub async fn stop_game<'a>(state: AppState<'a>) -> Result<(), ErrorCode> {
 
     let mut local_state = state.lock().await;
 
+    local_state.active_session = None;
+
+    local_state.game_process = None;
+
     let Some(mut runner) = local_state.game_runner.take() else {
         log::error!("stop_game: no game runner active");
 
@@ -114,12 +248,49 @@ pub async fn start_game<'a>(
 
     let game_store = get_game_response_from_store(&app_handler, game_id)?;
 
+    let game_store = check_dependencies(&app_handler, game_store, profile_id).await?;
+
     let profile = game_store
         .profiles
         .iter()
         .find(|p| p.id == profile_id)
         .ok_or(ErrorCode::NotFound)?;
 
+    // a cycle means the automatic order can't satisfy every dependency, so the
+    // written load order would be broken
+    if let Some(cycle) = &profile.dependency_cycle
+        && profile
+            .mods
+            .iter()
+            .any(|m| m.enabled && cycle.contains(&m.name))
+    {
+        log::error!(
+            "start_game: dependency cycle between enabled mods: {}",
+            cycle.join(" -> ")
+        );
+
+        return Err(ErrorCode::Conflict);
+    }
+
+    let mods_hash = links::mod_list_hash(&profile.mods);
+
+    // the game still starts, the UI offers to switch to the suggested profile
+    if let Some(compatibility) = save_name
+        .and_then(|name| game_store.saves.iter().find(|s| s.name == name))
+        .and_then(|save| SaveCompatibilityDto::new(save, &game_store.profiles, profile_id))
+        && compatibility.mods_match == Some(false)
+    {
+        log::warn!(
+            "start_game: save '{}' was written with a different mod list (suggested profile {:?})",
+            compatibility.save_name,
+            compatibility.suggested_profile_id
+        );
+
+        if let Err(e) = app_handler.emit(AppEvent::SaveModsMismatch.into(), compatibility) {
+            log::warn!("start_game: failed to emit the mod list mismatch: {:?}", e);
+        }
+    }
+
     let GameResponseDto {
         game_path,
 
@@ -130,9 +301,21 @@ pub async fn start_game<'a>(
         workshop_path,
 
         game_id,
+
+        runner,
+
+        mut profile_runners,
+
+        hooks,
         ..
     } = game_store;
 
+    let selected_runner = profile_runners.remove(&profile_id).unwrap_or(runner);
+
+    log::info!("start_game: runner={:?}", selected_runner);
+
+    log::info!("start_game: launch_options={:?}", profile.launch_options);
+
     log::info!("start_game paths: game_path={}", game_path.display());
 
     log::info!("start_game paths: mods_path={}", mods_path.display());
@@ -157,7 +340,9 @@ pub async fn start_game<'a>(
 
     log::info!("start_game: resolved savegame_path={:?}", savegame_path);
 
-    let txt_path = join_path!(&game_path, "used_mods.txt");
+    let script_name = mods::writer::script_file_name(profile_id);
+
+    let txt_path = join_path!(&game_path, &script_name);
 
     if !game_path.exists() {
         log::error!(
@@ -168,38 +353,195 @@ pub async fn start_game<'a>(
         return Err(ErrorCode::InternalError);
     }
 
+    let profile_ids: Vec<uuid::Uuid> = game_store.profiles.iter().map(|p| p.id).collect();
+
+    mods::writer::remove_stale_scripts(&game_path, &profile_ids);
+
     log::info!("start_game: writing mods to {}", txt_path.display());
 
     let mod_writer =
         mods::writer::ModWriter::new(game_id, &profile.mods, &mods_path, &workshop_path);
 
+    let hooks = LaunchHooks::new(
+        hooks,
+        HookContext {
+            game_id,
+
+            profile_id,
+
+            game_path: game_path.clone(),
+
+            mod_list_file: txt_path.clone(),
+
+            mods: mod_writer.mod_names(),
+        },
+    );
+
     mod_writer
         .write(txt_path)
         .expect("It wasn't possible to write the mod file");
 
-    let mut runner = launchers::GameLauncher::create(&app_handler).await;
+    let pre_launch_hooks = hooks.clone();
 
-    if let Err(e) = runner.launch_game(game_id, &game_path, savegame_path.as_ref()) {
-        log::error!("start_game: launch failed: {:?}", e);
+    tauri::async_runtime::spawn_blocking(move || pre_launch_hooks.pre_launch())
+        .await
+        .map_err(|e| {
+            log::error!("start_game: pre-launch hooks panicked: {:?}", e);
 
-        return Err(ErrorCode::InternalError);
-    }
+            ErrorCode::InternalError
+        })?
+        .map_err(|e| {
+            log::error!("start_game: launch aborted: {}", e);
+
+            ErrorCode::InternalError
+        })?;
+
+    // the pre-launch hooks ran, the post-exit ones clean up after them even when
+    // the game can't be started
+    let mut runner = match launchers::GameLauncher::create(&app_handler, &selected_runner).await {
+        Ok(runner) => runner,
+
+        Err(e) => {
+            log::error!("start_game: failed to prepare the launcher: {:?}", e);
+
+            run_post_exit_hooks(hooks);
+
+            return Err(ErrorCode::InternalError);
+        }
+    };
+
+    let launcher = match runner.launch_game(
+        game_id,
+        &game_path,
+        savegame_path.as_ref(),
+        &script_name,
+        &selected_runner,
+        &profile.launch_options,
+    ) {
+        Ok(launcher) => launcher,
+
+        Err(e) => {
+            log::error!("start_game: launch failed: {:?}", e);
+
+            run_post_exit_hooks(hooks);
+
+            return Err(ErrorCode::InternalError);
+        }
+    };
+
+    let game_info = DefaultGameInfo::find_by_id(game_id).ok_or(ErrorCode::NotFound)?;
+
+    let process = GameProcess {
+        launch_id: uuid::Uuid::new_v4(),
+
+        game_id,
+
+        profile_id,
+
+        save_name: savegame_path
+            .as_ref()
+            .and_then(|path| path.file_name())
+            .map(|name| name.to_string_lossy().to_string()),
+
+        pid: None,
+    };
 
     let mut state = state.lock().await;
 
+    state.game_process = Some(process.clone());
+
+    launchers::monitor::watch(
+        app_handler.clone(),
+        process,
+        game_info.executable_name,
+        launcher,
+        hooks,
+    );
+
     state.game_runner = Some(Box::new(runner));
 
+    state.active_session = Some(ActiveSession {
+        game_id,
+
+        profile_id,
+
+        mods_hash,
+
+        saves_path,
+    });
+
     log::info!("start_game: game launched successfully");
 
     Ok(())
 }
 
+fn run_post_exit_hooks(hooks: LaunchHooks) {
+    tauri::async_runtime::spawn_blocking(move || hooks.post_exit(SessionOutcome::NotStarted, None));
+}
+
+// Runs the pre-launch dependency validation and applies the user's policy. Returns
+// the game reloaded from the store when dependencies had to be enabled.
+async fn check_dependencies(
+    app_handler: &tauri::AppHandle,
+
+    game_store: GameResponseDto,
+
+    profile_id: uuid::Uuid,
+) -> Result<GameResponseDto, ErrorCode> {
+    let profile = game_store
+        .profiles
+        .iter()
+        .find(|p| p.id == profile_id)
+        .ok_or(ErrorCode::NotFound)?;
+
+    let problems = validation::validate_profile_mods(&profile.mods, &game_store);
+
+    if problems.is_empty() {
+        return Ok(game_store);
+    }
+
+    for problem in &problems {
+        log::warn!(
+            "start_game: {:?} '{}' for '{}'",
+            problem.kind,
+            problem.target,
+            problem.mod_name
+        );
+    }
+
+    let settings = SettingsStore::from_entries(SettingsStore::get_store(app_handler)?.entries())?;
+
+    match settings.dependency_policy {
+        DependencyPolicy::Warn => Ok(game_store),
+
+        DependencyPolicy::Block => {
+            log::error!(
+                "start_game: launch blocked by {} dependency problem(s)",
+                problems.len()
+            );
+
+            Err(ErrorCode::Conflict)
+        }
+
+        DependencyPolicy::AutoEnable => {
+            let enabled = Profile::get(app_handler, game_store.game_id, profile_id, |profile| {
+                Ok(validation::enable_dependencies(profile, &problems))
+            })
+            .await?;
+
+            if enabled == 0 {
+                return Ok(game_store);
+            }
+
+            get_game_response_from_store(app_handler, game_store.game_id)
+        }
+    }
+}
+
 #[tauri::command]
 
 // getting a game will also start its watchers.
 
-// TODO: heavy, too heavy... gotta trim it down or optimise it somehow. it's not the watchers
-
 pub async fn get_game(
     app_handle: tauri::AppHandle,
 
@@ -218,7 +560,7 @@ pub async fn get_game(
         crate::utils::steam::enrich_missing_workshop_names(&enrich_handle, game_id).await;
     });
 
-    let game_response = get_game_response_from_store(&app_handle, game_id)?;
+    let game_response = scan::with_progress(|| get_game_response_from_store(&app_handle, game_id))?;
 
     if let Some(profile_id) = game_response.default_profile {
         let response_mods: HashMap<&String, u32> = game_response
@@ -247,6 +589,7 @@ pub async fn get_game(
 
     start_game_watchers(
         app_state,
+        game_id,
         game_response.mods_path.clone(),
         &game_response.workshop_path,
         &game_response.saves_path,
@@ -283,11 +626,20 @@ pub async fn update_game(
 
         game.game_path = payload.game_path;
 
-        Ok(GameResponseDto::from_store(game.clone(), &steam_config))
+        Ok(scan::with_progress(|| {
+            GameResponseDto::from_store(game.clone(), &steam_config)
+        }))
     })
     .await?;
 
-    start_game_watchers(app_state, g.mods_path, &g.workshop_path, &g.saves_path).await;
+    start_game_watchers(
+        app_state,
+        game_id,
+        g.mods_path,
+        &g.workshop_path,
+        &g.saves_path,
+    )
+    .await;
 
     Ok(())
 }
@@ -295,6 +647,8 @@ pub async fn update_game(
 async fn start_game_watchers(
     app_state: AppState<'_>,
 
+    game_id: SupportedGames,
+
     mods_folder: PathBuf,
 
     workshop_folder: &Option<PathBuf>,
@@ -321,6 +675,7 @@ async fn start_game_watchers(
     let mut state = app_state.lock().await;
 
     state.folder_watcher.set_watchers(&folders);
+    state.watched_saves = saves_folder.clone().map(|saves| (game_id, saves));
 }
 
 #[tauri::command]
@@ -347,3 +702,161 @@ pub async fn set_default_profile(
     })
     .await
 }
+
+#[tauri::command]
+
+pub async fn check_save_profile(
+    app_handle: tauri::AppHandle,
+
+    game_id: SupportedGames,
+
+    profile_id: uuid::Uuid,
+
+    save_name: String,
+) -> Result<SaveCompatibilityDto, ErrorCode> {
+    let game_store = get_game_response_from_store(&app_handle, game_id)?;
+
+    let save = game_store
+        .saves
+        .iter()
+        .find(|s| s.name == save_name)
+        .ok_or(ErrorCode::NotFound)?;
+
+    SaveCompatibilityDto::new(save, &game_store.profiles, profile_id).ok_or(ErrorCode::NotFound)
+}
+
+#[tauri::command]
+
+pub async fn get_save_header(
+    app_handle: tauri::AppHandle,
+
+    game_id: SupportedGames,
+
+    save_name: String,
+) -> Result<SaveHeader, ErrorCode> {
+    let game = GameStore::from_entries(GameStore::get_store(&app_handle, game_id)?.entries())?;
+
+    let path = save_file_path(&game, &save_name)?;
+
+    // decoding the save reads the whole file, keep it off the async runtime
+    tauri::async_runtime::spawn_blocking(move || {
+        SaveHeader::read(&path).map_err(|e| {
+            log::error!("Failed to read save header {:?}: {:?}", path, e);
+
+            ErrorCode::InternalError
+        })
+    })
+    .await
+    .map_err(|e| {
+        log::error!("get_save_header: reading the header failed: {:?}", e);
+
+        ErrorCode::InternalError
+    })?
+}
+
+#[tauri::command]
+
+pub async fn get_save_backups(
+    app_handle: tauri::AppHandle,
+
+    game_id: SupportedGames,
+) -> Result<Vec<SaveBackupDto>, ErrorCode> {
+    backups::list_backups(&app_handle, game_id).map_err(|e| {
+        log::error!("Failed to list save backups for {}: {:?}", game_id, e);
+
+        ErrorCode::InternalError
+    })
+}
+
+#[tauri::command]
+
+pub async fn restore_save_backup<'a>(
+    app_handle: tauri::AppHandle,
+
+    state: AppState<'a>,
+
+    game_id: SupportedGames,
+
+    backup_id: String,
+) -> Result<(), ErrorCode> {
+    log::info!(
+        "restore_save_backup: game={:?}, backup={}",
+        game_id,
+        backup_id
+    );
+
+    ensure_game_not_running(&state).await?;
+
+    let saves_path = get_game_response_from_store(&app_handle, game_id)?
+        .saves_path
+        .ok_or(ErrorCode::NotFound)?;
+
+    let restored =
+        backups::restore_backup(&app_handle, game_id, &backup_id, &saves_path).map_err(|e| {
+            log::error!("restore_save_backup: {:?}", e);
+
+            match e.kind() {
+                std::io::ErrorKind::NotFound => ErrorCode::NotFound,
+
+                std::io::ErrorKind::InvalidInput => ErrorCode::BadRequest,
+
+                _ => ErrorCode::InternalError,
+            }
+        })?;
+
+    log::info!("restore_save_backup: restored {}", restored.display());
+
+    Ok(())
+}
+
+// sessions returned by `get_play_history` when no limit is given
+const DEFAULT_RECENT_SESSIONS: usize = 20;
+
+#[tauri::command]
+
+pub async fn get_play_history(
+    app_handle: tauri::AppHandle,
+
+    game_id: SupportedGames,
+
+    limit: Option<usize>,
+) -> Result<PlayHistoryDto, ErrorCode> {
+    let game = GameStore::from_entries(GameStore::get_store(&app_handle, game_id)?.entries())?;
+
+    Ok(PlayHistoryDto::new(
+        &game,
+        limit.unwrap_or(DEFAULT_RECENT_SESSIONS),
+    ))
+}
+
+#[tauri::command]
+
+pub async fn collect_diagnostics(
+    app_handle: tauri::AppHandle,
+
+    game_id: SupportedGames,
+
+    profile_id: Option<uuid::Uuid>,
+) -> Result<PathBuf, ErrorCode> {
+    log::info!(
+        "collect_diagnostics: game={:?}, profile={:?}",
+        game_id,
+        profile_id
+    );
+
+    // crash dumps can be large, keep the copy off the async runtime
+    tauri::async_runtime::spawn_blocking(move || {
+        diagnostics::collect(&app_handle, game_id, profile_id).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| {
+        log::error!("collect_diagnostics: task failed: {:?}", e);
+
+        ErrorCode::InternalError
+    })?
+    .map_err(|e| {
+        log::error!("collect_diagnostics: {}", e);
+
+        ErrorCode::InternalError
+    })
+}
@@ -1,6 +1,6 @@
 use crate::{
+    commands::helpers::retrieve_game_mods,
     dto::groups::GroupRequestDto,
-    mods::pack,
     stores::games::{GameStore, Group, Profile, ProfileModInfo, Store},
     supported_games::SupportedGames,
     utils::{ErrorCode, steam::SteamConfig},
@@ -103,7 +103,7 @@ pub async fn set_group_mods(
     group_id: uuid::Uuid,
     mods: Vec<String>,
 ) -> Result<serde_json::Value, ErrorCode> {
-    let steam_config = SteamConfig::from_app_handle(&app_handle)?;
+    let available_mod_names = available_mod_names(&app_handle, game_id)?;
 
     GameStore::get(&app_handle, game_id, |game| {
         let group = game
@@ -124,15 +124,6 @@ pub async fn set_group_mods(
             return Ok(serde_json::json!(&group.mods));
         }
 
-        let available_mod_names: HashSet<String> = if !added.is_empty() {
-            let workshop_path = steam_config.retrieve_steam_workshop_path(game_id);
-            let available_mods =
-                pack::ModPack::retrieve_mods(game.game_id, &game.mods_path, &workshop_path);
-            available_mods.into_iter().map(|m| m.name).collect()
-        } else {
-            HashSet::new()
-        };
-
         for profile in game.profiles.iter_mut() {
             if !profile.groups.contains(&group_id) {
                 continue;
@@ -164,7 +155,7 @@ pub async fn add_group_mods(
     group_id: uuid::Uuid,
     mods: Vec<String>,
 ) -> Result<serde_json::Value, ErrorCode> {
-    let steam_config = SteamConfig::from_app_handle(&app_handle)?;
+    let available_mod_names = available_mod_names(&app_handle, game_id)?;
 
     GameStore::get(&app_handle, game_id, |game| {
         let group = game
@@ -183,12 +174,6 @@ pub async fn add_group_mods(
             return Ok(serde_json::json!(&group.mods));
         }
 
-        let workshop_path = steam_config.retrieve_steam_workshop_path(game.game_id);
-        let available_mods =
-            pack::ModPack::retrieve_mods(game.game_id, &game.mods_path, &workshop_path);
-        let available_mod_names: HashSet<String> =
-            available_mods.into_iter().map(|m| m.name).collect();
-
         for profile in &mut game.profiles {
             if !profile.groups.contains(&group_id) {
                 continue;
@@ -251,7 +236,7 @@ pub async fn add_group_profile(
     group_id: uuid::Uuid,
     profile_id: uuid::Uuid,
 ) -> Result<serde_json::Value, ErrorCode> {
-    let steam_config = SteamConfig::from_app_handle(&app_handle)?;
+    let available_mod_names = available_mod_names(&app_handle, game_id)?;
 
     GameStore::get(&app_handle, game_id, |game| {
         let group = game
@@ -273,18 +258,7 @@ pub async fn add_group_profile(
         }
         profile.groups.push(group_id);
 
-        let workshop_path = steam_config.retrieve_steam_workshop_path(game.game_id);
-        let available_mods =
-            pack::ModPack::retrieve_mods(game.game_id, &game.mods_path, &workshop_path);
-        let available_mod_names: HashSet<String> =
-            available_mods.iter().map(|m| m.name.clone()).collect();
-
-        for mod_name in &group_mods {
-            let in_profile = profile.mods.iter().any(|m| &m.name == mod_name);
-            if in_profile || available_mod_names.contains(mod_name) {
-                profile_link_mod(profile, mod_name, group_id);
-            }
-        }
+        profile_link_group_mods(profile, group_id, &group_mods, &available_mod_names);
 
         Ok(serde_json::json!(&*profile))
     })
@@ -317,7 +291,7 @@ pub async fn set_groups_profile(
     profile_id: uuid::Uuid,
     groups: Vec<uuid::Uuid>,
 ) -> Result<serde_json::Value, ErrorCode> {
-    let steam_config = SteamConfig::from_app_handle(&app_handle)?;
+    let available_mod_names = available_mod_names(&app_handle, game_id)?;
 
     GameStore::get(&app_handle, game_id, |game| {
         let profile = game
@@ -338,15 +312,6 @@ pub async fn set_groups_profile(
             .copied()
             .collect();
 
-        let workshop_path = steam_config.retrieve_steam_workshop_path(game.game_id);
-        let available_mod_names: HashSet<String> = if !groups_to_add.is_empty() {
-            let available_mods =
-                pack::ModPack::retrieve_mods(game.game_id, &game.mods_path, &workshop_path);
-            available_mods.into_iter().map(|m| m.name).collect()
-        } else {
-            HashSet::new()
-        };
-
         // Link new groups
         for &group_id in &groups_to_add {
             let group_mods: Vec<String> = game
@@ -367,12 +332,7 @@ pub async fn set_groups_profile(
                 profile.groups.push(group_id);
             }
 
-            for mod_name in &group_mods {
-                let in_profile = profile.mods.iter().any(|m| &m.name == mod_name);
-                if in_profile || available_mod_names.contains(mod_name) {
-                    profile_link_mod(profile, mod_name, group_id);
-                }
-            }
+            profile_link_group_mods(profile, group_id, &group_mods, &available_mod_names);
         }
 
         // Unlink removed groups
@@ -391,6 +351,35 @@ pub async fn set_groups_profile(
     .await
 }
 
+/// Names of the installed packs. Scanned before `GameStore::get`, so store writes
+/// don't wait on the scan.
+pub(crate) fn available_mod_names(
+    app_handle: &tauri::AppHandle,
+    game_id: SupportedGames,
+) -> Result<HashSet<String>, ErrorCode> {
+    let steam_config = SteamConfig::from_app_handle(app_handle)?;
+
+    Ok(retrieve_game_mods(app_handle, game_id, &steam_config)?
+        .into_iter()
+        .map(|m| m.name)
+        .collect())
+}
+
+/// Link the group's mods that are installed or already in the profile.
+pub(crate) fn profile_link_group_mods(
+    profile: &mut Profile,
+    group_id: uuid::Uuid,
+    group_mods: &[String],
+    available_mod_names: &HashSet<String>,
+) {
+    for mod_name in group_mods {
+        let in_profile = profile.mods.iter().any(|m| &m.name == mod_name);
+        if in_profile || available_mod_names.contains(mod_name) {
+            profile_link_mod(profile, mod_name, group_id);
+        }
+    }
+}
+
 fn profile_link_mod(profile: &mut Profile, mod_name: &str, group_id: uuid::Uuid) {
     if let Some(existing) = profile.mods.iter_mut().find(|m| m.name == mod_name) {
         let groups = existing.groups.get_or_insert_with(Vec::new);
@@ -1,4 +1,5 @@
 use crate::dto::games::GameResponseDto;
+use crate::mods::{pack::ModPack, rules::LoadOrderRule};
 use crate::stores::games::GameStore;
 use crate::supported_games::SupportedGames;
 use crate::utils::ErrorCode;
@@ -16,3 +17,39 @@ pub fn get_game_response_from_store(
 
     Ok(game_store)
 }
+
+/// The game's load order rules followed by the masterlist's, its Workshop items
+/// matched through `game_mods`.
+pub fn load_order_rules(game: &GameStore, game_mods: &[ModPack]) -> Vec<LoadOrderRule> {
+    let workshop_ids: Vec<(String, u64)> = game_mods
+        .iter()
+        .filter_map(|m| Some((m.name.clone(), m.workshop_id()?)))
+        .collect();
+
+    game.load_order_rules
+        .iter()
+        .cloned()
+        .chain(
+            game.masterlist
+                .iter()
+                .flat_map(|masterlist| masterlist.rules(&workshop_ids)),
+        )
+        .collect()
+}
+
+// Parses every installed pack, so it's called before `GameStore::get` rather than
+// in it: store writes would otherwise wait for the whole scan.
+pub fn retrieve_game_mods(
+    app_handler: &tauri::AppHandle,
+    game_id: SupportedGames,
+    steam_config: &SteamConfig,
+) -> Result<Vec<ModPack>, ErrorCode> {
+    let game = GameStore::from_entries(GameStore::get_store(app_handler, game_id)?.entries())?;
+    let workshop_path = steam_config.retrieve_steam_workshop_path(game_id);
+
+    Ok(ModPack::retrieve_mods(
+        game_id,
+        &game.mods_path,
+        &workshop_path,
+    ))
+}
@@ -0,0 +1,148 @@
+use crate::{
+    dto::launchers::RunnerDto,
+    launchers::{
+        hooks::LaunchHook,
+        options::LaunchOptions,
+        runners::{self, RunnerSelection},
+    },
+    stores::games::{GameStore, Profile, Store},
+    supported_games::SupportedGames,
+    utils::{ErrorCode, steam::SteamConfig},
+};
+#[cfg(target_os = "linux")]
+use crate::{dto::launchers::UmuStatusDto, launchers::umu};
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub async fn get_umu_status(app_handle: tauri::AppHandle) -> UmuStatusDto {
+    umu::status(&app_handle)
+}
+
+/// Download the latest (or pinned) umu-launcher release. Launches never update
+/// it on their own.
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub async fn update_umu_launcher(app_handle: tauri::AppHandle) -> Result<UmuStatusDto, ErrorCode> {
+    log::info!("update_umu_launcher requested");
+
+    if let Err(e) = umu::update(&app_handle).await {
+        log::error!("update_umu_launcher: {}", e);
+        return Err(ErrorCode::InternalError);
+    }
+
+    Ok(umu::status(&app_handle))
+}
+
+#[tauri::command]
+pub async fn get_runners(app_handle: tauri::AppHandle) -> Result<Vec<RunnerDto>, ErrorCode> {
+    let steam_config = SteamConfig::from_app_handle(&app_handle)?;
+    Ok(runners::discover(&steam_config))
+}
+
+#[tauri::command]
+pub async fn set_game_runner(
+    app_handle: tauri::AppHandle,
+    game_id: SupportedGames,
+    runner: RunnerSelection,
+) -> Result<(), ErrorCode> {
+    log::info!("set_game_runner: game={:?}, runner={:?}", game_id, runner);
+
+    runner.validate().map_err(|e| {
+        log::warn!("set_game_runner: {}", e);
+        ErrorCode::BadRequest
+    })?;
+
+    GameStore::get(&app_handle, game_id, |game| {
+        game.runner = runner;
+        Ok(())
+    })
+    .await
+}
+
+/// Override the game's runner for one profile; `None` goes back to the game's.
+#[tauri::command]
+pub async fn set_profile_runner(
+    app_handle: tauri::AppHandle,
+    game_id: SupportedGames,
+    profile_id: uuid::Uuid,
+    runner: Option<RunnerSelection>,
+) -> Result<(), ErrorCode> {
+    log::info!(
+        "set_profile_runner: game={:?}, profile={}, runner={:?}",
+        game_id,
+        profile_id,
+        runner
+    );
+
+    if let Some(runner) = &runner {
+        runner.validate().map_err(|e| {
+            log::warn!("set_profile_runner: {}", e);
+            ErrorCode::BadRequest
+        })?;
+    }
+
+    GameStore::get(&app_handle, game_id, |game| {
+        if !game.profiles.iter().any(|p| p.id == profile_id) {
+            return Err(ErrorCode::NotFound);
+        }
+
+        match runner {
+            Some(runner) => game.profile_runners.insert(profile_id, runner),
+            None => game.profile_runners.remove(&profile_id),
+        };
+
+        Ok(())
+    })
+    .await
+}
+
+/// Replace the game's pre-launch and post-exit hooks.
+#[tauri::command]
+pub async fn set_game_hooks(
+    app_handle: tauri::AppHandle,
+    game_id: SupportedGames,
+    hooks: Vec<LaunchHook>,
+) -> Result<(), ErrorCode> {
+    log::info!("set_game_hooks: game={:?}, hooks={:?}", game_id, hooks);
+
+    for hook in &hooks {
+        hook.validate().map_err(|e| {
+            log::warn!("set_game_hooks: {}", e);
+            ErrorCode::BadRequest
+        })?;
+    }
+
+    GameStore::get(&app_handle, game_id, |game| {
+        game.hooks = hooks;
+        Ok(())
+    })
+    .await
+}
+
+/// Extra arguments, environment variables and wrapper commands for a profile's
+/// launches.
+#[tauri::command]
+pub async fn set_profile_launch_options(
+    app_handle: tauri::AppHandle,
+    game_id: SupportedGames,
+    profile_id: uuid::Uuid,
+    options: LaunchOptions,
+) -> Result<(), ErrorCode> {
+    log::info!(
+        "set_profile_launch_options: game={:?}, profile={}, options={:?}",
+        game_id,
+        profile_id,
+        options
+    );
+
+    options.validate().map_err(|e| {
+        log::warn!("set_profile_launch_options: {}", e);
+        ErrorCode::BadRequest
+    })?;
+
+    Profile::get(&app_handle, game_id, profile_id, |profile| {
+        profile.launch_options = options;
+        Ok(())
+    })
+    .await
+}
@@ -1,5 +1,7 @@
 pub mod games;
 pub mod groups;
 pub mod helpers;
+pub mod launchers;
+pub mod mods;
 pub mod profiles;
 pub mod settings;
@@ -0,0 +1,155 @@
+use crate::{
+    commands::helpers::get_game_response_from_store,
+    dto::{conflicts::ConflictReportDto, validation::ValidationProblemDto},
+    mods::{
+        conflicts::ConflictAnalyzer,
+        masterlist::{self, Masterlist, MasterlistSource},
+        rules::LoadOrderRule,
+        schema, validation,
+    },
+    stores::games::GameStore,
+    supported_games::SupportedGames,
+    utils::ErrorCode,
+};
+
+#[tauri::command]
+pub async fn get_profile_conflicts(
+    app_handle: tauri::AppHandle,
+    game_id: SupportedGames,
+    profile_id: uuid::Uuid,
+) -> Result<ConflictReportDto, ErrorCode> {
+    log::info!(
+        "get_profile_conflicts: game={:?}, profile={}",
+        game_id,
+        profile_id
+    );
+
+    let game = get_game_response_from_store(&app_handle, game_id)?;
+
+    let profile = game
+        .profiles
+        .into_iter()
+        .find(|p| p.id == profile_id)
+        .ok_or(ErrorCode::NotFound)?;
+
+    // indexing opens every enabled pack, keep it off the async runtime
+    tauri::async_runtime::spawn_blocking(move || {
+        let schema = schema::load(&app_handle, game_id);
+        ConflictAnalyzer::new(game_id, &profile.mods, schema.as_deref()).report(profile.id)
+    })
+    .await
+    .map_err(|e| {
+        log::error!("get_profile_conflicts: analysis failed: {:?}", e);
+        ErrorCode::InternalError
+    })
+}
+
+#[tauri::command]
+pub async fn validate_profile(
+    app_handle: tauri::AppHandle,
+    game_id: SupportedGames,
+    profile_id: uuid::Uuid,
+) -> Result<Vec<ValidationProblemDto>, ErrorCode> {
+    let game = get_game_response_from_store(&app_handle, game_id)?;
+
+    let profile = game
+        .profiles
+        .iter()
+        .find(|p| p.id == profile_id)
+        .ok_or(ErrorCode::NotFound)?;
+
+    Ok(validation::validate_profile_mods(&profile.mods, &game))
+}
+
+/// Replace the game's load order rules. Profiles in automatic mode are sorted
+/// with them the next time they're loaded.
+#[tauri::command]
+pub async fn set_load_order_rules(
+    app_handle: tauri::AppHandle,
+    game_id: SupportedGames,
+    rules: Vec<LoadOrderRule>,
+) -> Result<(), ErrorCode> {
+    log::info!(
+        "set_load_order_rules: game={:?}, rules={:?}",
+        game_id,
+        rules
+    );
+
+    for rule in &rules {
+        rule.validate().map_err(|e| {
+            log::warn!("set_load_order_rules: {}", e);
+            ErrorCode::BadRequest
+        })?;
+    }
+
+    GameStore::get(&app_handle, game_id, |game| {
+        game.load_order_rules = rules;
+        Ok(())
+    })
+    .await
+}
+
+/// Import a masterlist for the game, replacing the previous one. The list is
+/// stored parsed, so only importing from a URL needs the network.
+#[tauri::command]
+pub async fn import_masterlist(
+    app_handle: tauri::AppHandle,
+    game_id: SupportedGames,
+    source: MasterlistSource,
+) -> Result<usize, ErrorCode> {
+    log::info!("import_masterlist: game={:?}, source={:?}", game_id, source);
+
+    if let MasterlistSource::File { path } = &source
+        && !path.is_file()
+    {
+        log::warn!("import_masterlist: {} not found", path.display());
+        return Err(ErrorCode::NotFound);
+    }
+
+    let read_source = source.clone();
+    let contents = tauri::async_runtime::spawn_blocking(move || {
+        masterlist::read(&read_source).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| {
+        log::error!("import_masterlist: read task failed: {:?}", e);
+        ErrorCode::InternalError
+    })?
+    .map_err(|e| {
+        log::error!("import_masterlist: failed to read the masterlist: {}", e);
+        ErrorCode::InternalError
+    })?;
+
+    let masterlist = Masterlist::parse(source, &contents).map_err(|e| {
+        log::warn!("import_masterlist: invalid masterlist: {}", e);
+        ErrorCode::BadRequest
+    })?;
+
+    let entries = masterlist.entries.len();
+
+    log::info!(
+        "Imported masterlist {:?} with {} entries",
+        masterlist.name,
+        entries
+    );
+
+    GameStore::get(&app_handle, game_id, |game| {
+        game.masterlist = Some(masterlist);
+        Ok(entries)
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn remove_masterlist(
+    app_handle: tauri::AppHandle,
+    game_id: SupportedGames,
+) -> Result<(), ErrorCode> {
+    log::info!("remove_masterlist: game={:?}", game_id);
+
+    GameStore::get(&app_handle, game_id, |game| {
+        game.masterlist = None;
+        Ok(())
+    })
+    .await
+}
@@ -1,8 +1,34 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+
 use crate::{
-    dto::{mods::ModRequestDto, profiles::ProfileRequestDto},
-    stores::games::{GameStore, Profile, ProfileModInfo, Store},
+    commands::{
+        games::save_file_path,
+        groups::{available_mod_names, profile_link_group_mods},
+        helpers::{load_order_rules, retrieve_game_mods},
+    },
+    defaults::games::DefaultGameInfo,
+    dto::{
+        diff::ProfileDiffDto,
+        imports::{ProfileExportResponseDto, ProfileImportResponseDto},
+        mods::ModRequestDto,
+        profiles::{ProfileRequestDto, ProfileResponseDto},
+    },
+    launchers::options::LaunchOptions,
+    mods::{
+        diff,
+        import::{self, ImportedMod},
+        merge::{self, MergeStrategy},
+        pack,
+        share::ProfileExport,
+        writer,
+    },
+    saves::header::SaveHeader,
+    stores::games::{GameStore, Group, Profile, ProfileModInfo, ProfileSnapshot, Store},
     supported_games::SupportedGames,
-    utils::ErrorCode,
+    utils::{ErrorCode, path::retrieve_saves_absolute_path, steam::SteamConfig},
 };
 
 #[tauri::command]
@@ -36,7 +62,10 @@ pub async fn update_profile(
     Profile::get(&app_handle, game_id, profile_id, |profile| {
         payload.id = Some(profile_id);
 
+        // launch options aren't part of the payload, they have their own command
+        let launch_options = std::mem::take(&mut profile.launch_options);
         *profile = Profile::from(payload);
+        profile.launch_options = launch_options;
 
         Ok(serde_json::json!(profile))
     })
@@ -112,6 +141,11 @@ pub async fn delete_profile(
 
         game.profiles.retain(|p| p.id != profile_id);
 
+        game.profile_runners.remove(&profile_id);
+
+        let profile_ids: Vec<uuid::Uuid> = game.profiles.iter().map(|p| p.id).collect();
+        writer::remove_stale_scripts(&game.game_path, &profile_ids);
+
         Ok(())
     })
     .await
@@ -190,3 +224,589 @@ pub async fn remove_profile_mods(
     })
     .await
 }
+
+#[tauri::command]
+pub async fn import_launcher_profile(
+    app_handle: tauri::AppHandle,
+    game_id: SupportedGames,
+    name: String,
+) -> Result<serde_json::Value, ErrorCode> {
+    log::info!("import_launcher_profile: game={:?}, name={}", game_id, name);
+
+    let game_info = DefaultGameInfo::find_by_id(game_id).ok_or(ErrorCode::NotFound)?;
+    let steam_config = SteamConfig::from_app_handle(&app_handle)?;
+
+    let moddata_path =
+        retrieve_saves_absolute_path(game_id, game_info.launcher_moddata_path, &steam_config)
+            .filter(|path| path.exists())
+            .ok_or_else(|| {
+                log::warn!("import_launcher_profile: launcher mod list not found");
+                ErrorCode::NotFound
+            })?;
+
+    let imported = import::read_launcher_moddata(&moddata_path, game_info.launcher_game_key)
+        .map_err(|e| {
+            log::error!(
+                "import_launcher_profile: failed to read {}: {:?}",
+                moddata_path.display(),
+                e
+            );
+            ErrorCode::InternalError
+        })?;
+
+    import_profile(
+        &app_handle,
+        game_id,
+        ImportTarget::New(name),
+        imported,
+        vec![],
+        &steam_config,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn import_mod_script(
+    app_handle: tauri::AppHandle,
+    game_id: SupportedGames,
+    path: PathBuf,
+    profile_id: Option<uuid::Uuid>,
+    name: Option<String>,
+) -> Result<serde_json::Value, ErrorCode> {
+    log::info!(
+        "import_mod_script: game={:?}, path={}, profile={:?}",
+        game_id,
+        path.display(),
+        profile_id
+    );
+
+    let contents = std::fs::read_to_string(&path).map_err(|e| {
+        log::error!(
+            "import_mod_script: failed to read {}: {:?}",
+            path.display(),
+            e
+        );
+        ErrorCode::NotFound
+    })?;
+
+    let script = import::parse_mod_script(&contents);
+    let steam_config = SteamConfig::from_app_handle(&app_handle)?;
+
+    let target = match profile_id {
+        Some(profile_id) => ImportTarget::Existing(profile_id),
+        None => ImportTarget::New(name.unwrap_or_else(|| {
+            path.file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| String::from("Imported"))
+        })),
+    };
+
+    import_profile(
+        &app_handle,
+        game_id,
+        target,
+        script.mods,
+        script.workshop_ids,
+        &steam_config,
+    )
+    .await
+}
+
+/// Rebuild a profile from the pack list stored in a save, so an old campaign can
+/// be continued with the mods it was started with.
+#[tauri::command]
+pub async fn create_profile_from_save(
+    app_handle: tauri::AppHandle,
+    game_id: SupportedGames,
+    save_name: String,
+    name: Option<String>,
+) -> Result<serde_json::Value, ErrorCode> {
+    log::info!(
+        "create_profile_from_save: game={:?}, save={}",
+        game_id,
+        save_name
+    );
+
+    let game = GameStore::from_entries(GameStore::get_store(&app_handle, game_id)?.entries())?;
+    let path = save_file_path(&game, &save_name)?;
+
+    // decoding the save reads the whole file, keep it off the async runtime
+    let header_path = path.clone();
+    let header = tauri::async_runtime::spawn_blocking(move || SaveHeader::read(&header_path))
+        .await
+        .map_err(|e| {
+            log::error!(
+                "create_profile_from_save: reading the header failed: {:?}",
+                e
+            );
+            ErrorCode::InternalError
+        })?
+        .map_err(|e| {
+            log::error!(
+                "create_profile_from_save: failed to read {}: {:?}",
+                path.display(),
+                e
+            );
+            ErrorCode::InternalError
+        })?;
+
+    if header.packs.is_empty() {
+        log::warn!(
+            "create_profile_from_save: no pack list found in {}",
+            save_name
+        );
+        return Err(ErrorCode::BadRequest);
+    }
+
+    let imported = header
+        .packs
+        .into_iter()
+        .map(|name| ImportedMod {
+            name,
+            enabled: true,
+        })
+        .collect();
+
+    let name = name.unwrap_or_else(|| {
+        path.file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or(save_name)
+    });
+    let steam_config = SteamConfig::from_app_handle(&app_handle)?;
+
+    import_profile(
+        &app_handle,
+        game_id,
+        ImportTarget::New(name),
+        imported,
+        vec![],
+        &steam_config,
+    )
+    .await
+}
+
+enum ImportTarget {
+    New(String),
+    Existing(uuid::Uuid),
+}
+
+// Imported load orders are stored in manual mode, so their order is kept as is.
+async fn import_profile(
+    app_handle: &tauri::AppHandle,
+    game_id: SupportedGames,
+    target: ImportTarget,
+    imported: Vec<ImportedMod>,
+    workshop_ids: Vec<u64>,
+    steam_config: &SteamConfig,
+) -> Result<serde_json::Value, ErrorCode> {
+    let disk_mods = retrieve_game_mods(app_handle, game_id, steam_config)?;
+
+    let subscribed: Vec<u64> = steam_config
+        .retrieve_steam_workshop_path(game_id)
+        .map(|path| {
+            pack::ModPack::scan_workshop_ids(&path)
+                .into_iter()
+                .map(|(_, id)| id)
+                .collect()
+        })
+        .unwrap_or_default();
+    let missing_workshop_ids: Vec<u64> = workshop_ids
+        .into_iter()
+        .filter(|id| !subscribed.contains(id))
+        .collect();
+
+    GameStore::get(app_handle, game_id, |game| {
+        if let ImportTarget::New(name) = &target
+            && game.profiles.iter().any(|p| &p.name == name)
+        {
+            return Err(ErrorCode::Conflict);
+        }
+
+        let (mods, missing) = import::resolve_mods(imported, &disk_mods);
+
+        let profile = match target {
+            ImportTarget::New(name) => {
+                let profile = Profile {
+                    id: uuid::Uuid::new_v4(),
+                    name,
+                    mods,
+                    manual_mode: true,
+                    groups: vec![],
+                    launch_options: LaunchOptions::default(),
+                };
+                game.profiles.push(profile.clone());
+                profile
+            }
+            ImportTarget::Existing(profile_id) => {
+                let profile = game
+                    .profiles
+                    .iter_mut()
+                    .find(|p| p.id == profile_id)
+                    .ok_or(ErrorCode::NotFound)?;
+                import::apply_to_profile(profile, mods);
+                profile.clone()
+            }
+        };
+
+        log::info!(
+            "Imported {} mod(s) into profile '{}', {} missing",
+            profile.mods.iter().filter(|m| m.enabled).count(),
+            profile.name,
+            missing.len()
+        );
+
+        Ok(serde_json::json!(ProfileImportResponseDto {
+            profile,
+            missing,
+            missing_workshop_ids,
+        }))
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn export_profile(
+    app_handle: tauri::AppHandle,
+    game_id: SupportedGames,
+    profile_id: uuid::Uuid,
+) -> Result<ProfileExportResponseDto, ErrorCode> {
+    let steam_config = SteamConfig::from_app_handle(&app_handle)?;
+    let game = GameStore::from_entries(GameStore::get_store(&app_handle, game_id)?.entries())?;
+
+    let profile = game
+        .profiles
+        .iter()
+        .find(|p| p.id == profile_id)
+        .ok_or(ErrorCode::NotFound)?;
+
+    let workshop_ids: HashMap<String, u64> = steam_config
+        .retrieve_steam_workshop_path(game_id)
+        .map(|path| {
+            pack::ModPack::scan_workshop_ids(&path)
+                .into_iter()
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let export = ProfileExport::new(&game, profile, &workshop_ids);
+    let share_code = export.to_share_code().map_err(|e| {
+        log::error!("export_profile: failed to encode share code: {:?}", e);
+        ErrorCode::InternalError
+    })?;
+
+    Ok(ProfileExportResponseDto { export, share_code })
+}
+
+#[tauri::command]
+pub async fn import_shared_profile(
+    app_handle: tauri::AppHandle,
+    game_id: SupportedGames,
+    payload: String, // share code or export JSON
+    name: Option<String>,
+) -> Result<serde_json::Value, ErrorCode> {
+    let export = ProfileExport::parse(&payload).map_err(|e| {
+        log::warn!("import_shared_profile: invalid profile export: {:?}", e);
+        ErrorCode::BadRequest
+    })?;
+
+    if export.game_id != game_id {
+        log::warn!(
+            "import_shared_profile: export is for game {:?}, not {:?}",
+            export.game_id,
+            game_id
+        );
+        return Err(ErrorCode::BadRequest);
+    }
+
+    let steam_config = SteamConfig::from_app_handle(&app_handle)?;
+    let name = name.unwrap_or_else(|| export.name.clone());
+    let disk_mods = retrieve_game_mods(&app_handle, game_id, &steam_config)?;
+
+    GameStore::get(&app_handle, game_id, |game| {
+        if game.profiles.iter().any(|p| p.name == name) {
+            return Err(ErrorCode::Conflict);
+        }
+
+        let (mut mods, missing) = import::resolve_mods(export.imported_mods(), &disk_mods);
+
+        // groups are matched by name, the missing ones are created
+        let mut group_ids: Vec<(String, uuid::Uuid)> = vec![];
+        for exported in &export.groups {
+            let id = match game.groups.iter().find(|g| g.name == exported.name) {
+                Some(group) => group.id,
+                None => {
+                    let group = Group {
+                        id: uuid::Uuid::new_v4(),
+                        name: exported.name.clone(),
+                        mods: exported.mods.clone(),
+                    };
+                    let id = group.id;
+                    game.groups.push(group);
+                    id
+                }
+            };
+            group_ids.push((exported.name.clone(), id));
+        }
+
+        for m in mods.iter_mut() {
+            let Some(exported) = export.mods.iter().find(|e| e.name == m.name) else {
+                continue;
+            };
+
+            let groups: Vec<uuid::Uuid> = group_ids
+                .iter()
+                .filter(|(name, _)| exported.groups.contains(name))
+                .map(|(_, id)| *id)
+                .collect();
+            m.groups = (!groups.is_empty()).then_some(groups);
+        }
+
+        // local renames win over the shared ones
+        for exported in &export.mods {
+            if let Some(custom_name) = &exported.custom_name {
+                game.mod_custom_names
+                    .entry(exported.name.clone())
+                    .or_insert_with(|| custom_name.clone());
+            }
+        }
+
+        let missing_workshop_ids: Vec<u64> = export
+            .mods
+            .iter()
+            .filter(|m| missing.contains(&m.name))
+            .filter_map(|m| m.workshop_id)
+            .collect();
+
+        let profile = Profile {
+            id: uuid::Uuid::new_v4(),
+            name,
+            mods,
+            manual_mode: export.manual_mode,
+            groups: group_ids.into_iter().map(|(_, id)| id).collect(),
+            launch_options: LaunchOptions::default(),
+        };
+        game.profiles.push(profile.clone());
+
+        log::info!(
+            "Imported shared profile '{}': {} mod(s), {} missing ({} workshop item(s) to subscribe)",
+            profile.name,
+            profile.mods.len(),
+            missing.len(),
+            missing_workshop_ids.len()
+        );
+
+        Ok(serde_json::json!(ProfileImportResponseDto {
+            profile,
+            missing,
+            missing_workshop_ids,
+        }))
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn diff_profiles(
+    app_handle: tauri::AppHandle,
+    game_id: SupportedGames,
+    profile_a: uuid::Uuid,
+    profile_b: uuid::Uuid,
+) -> Result<ProfileDiffDto, ErrorCode> {
+    let game = GameStore::from_entries(GameStore::get_store(&app_handle, game_id)?.entries())?;
+    let find = |id: uuid::Uuid| {
+        game.profiles
+            .iter()
+            .find(|p| p.id == id)
+            .cloned()
+            .ok_or(ErrorCode::NotFound)
+    };
+    let a = find(profile_a)?;
+    let b = find(profile_b)?;
+
+    // automatic profiles are compared in the order they're loaded in, their
+    // stored order goes stale as packs change
+    let steam_config = SteamConfig::from_app_handle(&app_handle)?;
+    let game_mods = retrieve_game_mods(&app_handle, game_id, &steam_config)?;
+    let rules = load_order_rules(&game, &game_mods);
+
+    Ok(diff::diff_mods(
+        &ProfileResponseDto::sorted_mods(a, &game_mods, &rules),
+        &ProfileResponseDto::sorted_mods(b, &game_mods, &rules),
+    ))
+}
+
+#[tauri::command]
+pub async fn merge_profiles(
+    app_handle: tauri::AppHandle,
+    game_id: SupportedGames,
+    source_id: uuid::Uuid,
+    target_id: uuid::Uuid,
+    strategy: MergeStrategy,
+) -> Result<serde_json::Value, ErrorCode> {
+    log::info!(
+        "merge_profiles: game={:?}, source={}, target={}, strategy={:?}",
+        game_id,
+        source_id,
+        target_id,
+        strategy
+    );
+
+    // an intersection only keeps groups the target already links, the other
+    // strategies can link new groups whose installed mods are added
+    let available_mod_names = match strategy {
+        MergeStrategy::Intersection => HashSet::new(),
+        _ => available_mod_names(&app_handle, game_id)?,
+    };
+
+    GameStore::get(&app_handle, game_id, |game| {
+        let find = |id: uuid::Uuid| {
+            game.profiles
+                .iter()
+                .find(|p| p.id == id)
+                .cloned()
+                .ok_or(ErrorCode::NotFound)
+        };
+        let source = find(source_id)?;
+        let target = find(target_id)?;
+
+        let groups = merge::merge_groups(&source, &target, strategy);
+        let mods = merge::merge_mods(&source, &target, strategy, &groups);
+
+        let new_groups: Vec<(uuid::Uuid, Vec<String>)> = game
+            .groups
+            .iter()
+            .filter(|g| groups.contains(&g.id) && !target.groups.contains(&g.id))
+            .map(|g| (g.id, g.mods.clone()))
+            .collect();
+
+        let profile = game
+            .profiles
+            .iter_mut()
+            .find(|p| p.id == target_id)
+            .unwrap();
+
+        profile.mods = mods;
+        profile.groups = groups;
+
+        for (group_id, group_mods) in &new_groups {
+            profile_link_group_mods(profile, *group_id, group_mods, &available_mod_names);
+        }
+
+        Ok(serde_json::json!(&*profile))
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn get_profile_snapshots(
+    app_handle: tauri::AppHandle,
+    game_id: SupportedGames,
+    profile_id: uuid::Uuid,
+) -> Result<Vec<ProfileSnapshot>, ErrorCode> {
+    let game = GameStore::from_entries(GameStore::get_store(&app_handle, game_id)?.entries())?;
+
+    if !game.profiles.iter().any(|p| p.id == profile_id) {
+        return Err(ErrorCode::NotFound);
+    }
+
+    let mut snapshots = game
+        .profile_snapshots
+        .get(&profile_id)
+        .cloned()
+        .unwrap_or_default();
+    snapshots.reverse(); // newest first
+
+    Ok(snapshots)
+}
+
+#[tauri::command]
+pub async fn restore_profile_snapshot(
+    app_handle: tauri::AppHandle,
+    game_id: SupportedGames,
+    profile_id: uuid::Uuid,
+    snapshot_id: uuid::Uuid,
+) -> Result<serde_json::Value, ErrorCode> {
+    log::info!(
+        "restore_profile_snapshot: game={:?}, profile={}, snapshot={}",
+        game_id,
+        profile_id,
+        snapshot_id
+    );
+
+    // the restore itself is snapshotted like any other change, so it can be undone
+    GameStore::get(&app_handle, game_id, |game| {
+        let snapshot = game
+            .profile_snapshots
+            .get(&profile_id)
+            .and_then(|snapshots| snapshots.iter().find(|s| s.id == snapshot_id))
+            .cloned()
+            .ok_or(ErrorCode::NotFound)?;
+
+        // groups deleted since the snapshot was taken can't be linked again
+        let existing_groups: Vec<uuid::Uuid> = game.groups.iter().map(|g| g.id).collect();
+
+        let profile = game
+            .profiles
+            .iter_mut()
+            .find(|p| p.id == profile_id)
+            .ok_or(ErrorCode::NotFound)?;
+
+        profile.manual_mode = snapshot.manual_mode;
+        profile.groups = snapshot
+            .groups
+            .into_iter()
+            .filter(|g| existing_groups.contains(g))
+            .collect();
+        profile.mods = snapshot.mods;
+
+        for m in profile.mods.iter_mut() {
+            if let Some(groups) = &mut m.groups {
+                groups.retain(|g| existing_groups.contains(g));
+                if groups.is_empty() {
+                    m.groups = None;
+                }
+            }
+        }
+
+        Ok(serde_json::json!(&*profile))
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn diff_profile_snapshot(
+    app_handle: tauri::AppHandle,
+    game_id: SupportedGames,
+    profile_id: uuid::Uuid,
+    snapshot_id: uuid::Uuid,
+) -> Result<ProfileDiffDto, ErrorCode> {
+    let game = GameStore::from_entries(GameStore::get_store(&app_handle, game_id)?.entries())?;
+
+    let profile = game
+        .profiles
+        .iter()
+        .find(|p| p.id == profile_id)
+        .ok_or(ErrorCode::NotFound)?;
+
+    let snapshot = game
+        .profile_snapshots
+        .get(&profile_id)
+        .and_then(|snapshots| snapshots.iter().find(|s| s.id == snapshot_id))
+        .ok_or(ErrorCode::NotFound)?;
+
+    let snapshot_profile = Profile {
+        mods: snapshot.mods.clone(),
+        manual_mode: snapshot.manual_mode,
+        ..profile.clone()
+    };
+
+    // both in the order they're loaded in, see `diff_profiles`
+    let steam_config = SteamConfig::from_app_handle(&app_handle)?;
+    let game_mods = retrieve_game_mods(&app_handle, game_id, &steam_config)?;
+    let rules = load_order_rules(&game, &game_mods);
+
+    // a = the snapshot, b = the current state
+    Ok(diff::diff_mods(
+        &ProfileResponseDto::sorted_mods(snapshot_profile, &game_mods, &rules),
+        &ProfileResponseDto::sorted_mods(profile.clone(), &game_mods, &rules),
+    ))
+}
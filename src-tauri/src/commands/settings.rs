@@ -1,5 +1,8 @@
 use crate::dto::settings::{UpdateUserSettingsDto, UserSettingsResponseDto};
 use crate::events::AppEvent;
+use crate::launchers::runners::UmuSource;
+use crate::mods::validation::DependencyPolicy;
+use crate::saves::backups::SaveBackupSettings;
 use crate::stores::settings::{SettingsKey, SettingsStore};
 use crate::supported_games::SupportedGames;
 use crate::utils::ErrorCode;
@@ -55,6 +58,66 @@ pub async fn set_invert_mod_names(
     Ok(())
 }
 
+#[tauri::command]
+pub async fn set_dependency_policy(
+    app_handle: tauri::AppHandle,
+    policy: DependencyPolicy,
+) -> Result<(), ErrorCode> {
+    let store = SettingsStore::get_store(&app_handle)?;
+
+    store.set(SettingsKey::DependencyPolicy, serde_json::json!(policy));
+    store.save().map_err(|e| {
+        log::error!("Failed to save settings store: {:?}", e);
+        ErrorCode::InternalError
+    })?;
+
+    app_handle
+        .emit(AppEvent::UpdateUserSettings.into(), ())
+        .expect("Failed to emit update_user_settings event");
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_save_backups(
+    app_handle: tauri::AppHandle,
+    settings: SaveBackupSettings,
+) -> Result<(), ErrorCode> {
+    let store = SettingsStore::get_store(&app_handle)?;
+
+    store.set(SettingsKey::SaveBackups, serde_json::json!(settings));
+    store.save().map_err(|e| {
+        log::error!("Failed to save settings store: {:?}", e);
+        ErrorCode::InternalError
+    })?;
+
+    app_handle
+        .emit(AppEvent::UpdateUserSettings.into(), ())
+        .expect("Failed to emit update_user_settings event");
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_umu_source(
+    app_handle: tauri::AppHandle,
+    source: UmuSource,
+) -> Result<(), ErrorCode> {
+    let store = SettingsStore::get_store(&app_handle)?;
+
+    store.set(SettingsKey::UmuSource, serde_json::json!(source));
+    store.save().map_err(|e| {
+        log::error!("Failed to save settings store: {:?}", e);
+        ErrorCode::InternalError
+    })?;
+
+    app_handle
+        .emit(AppEvent::UpdateUserSettings.into(), ())
+        .expect("Failed to emit update_user_settings event");
+
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn update_settings(
     app_handle: tauri::AppHandle,
@@ -8,6 +8,9 @@ pub struct DefaultGameInfo {
     pub executable_name: &'static str,
     pub mods_path: &'static str, // relative path. It will be appended to the game_path to get the full path to the mods folder. Probably won't be changed ever
     pub saves_path: &'static str, // the default root should be the roaming folder (on Linux it is relative to proton's prefix, es: /home/<username>/.local/share/Steam/steamapps/compatdata/<gameid>/pfx/drive_c/users/steamuser/AppData/Roaming)
+    pub launcher_moddata_path: &'static str, // mod list kept by the official CA launcher, relative to the roaming folder like saves_path
+    pub launcher_game_key: &'static str, // value of the `game` field for this game's entries in the launcher mod list
+    pub app_data_path: &'static str, // the game's own folder in the roaming folder, where it writes crash dumps and logs
 }
 impl DefaultGameInfo {
     pub fn get_game_path(&self, steam_config: &SteamConfig) -> Option<PathBuf> {
@@ -42,4 +45,7 @@ pub const SUPPORTED_GAMES: [DefaultGameInfo; 1] = [DefaultGameInfo {
     executable_name: "Warhammer3.exe",
     mods_path: "data/",
     saves_path: "/The Creative Assembly/Warhammer3/save_games/",
+    launcher_moddata_path: "/The Creative Assembly/Launcher/20190104-moddata.dat",
+    launcher_game_key: "warhammer3",
+    app_data_path: "/The Creative Assembly/Warhammer3/",
 }];
@@ -0,0 +1,264 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use tauri::Manager;
+use zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+use crate::{
+    defaults::games::DefaultGameInfo,
+    mods::{pack::ModPack, share::ProfileExport, writer},
+    stores::games::GameStore,
+    supported_games::SupportedGames,
+    utils::{path::retrieve_saves_absolute_path, steam::SteamConfig},
+};
+
+// Only files written around the crash are useful, and old dumps add up quickly.
+const MAX_FILE_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+const MAX_FILE_SIZE: u64 = 256 * 1024 * 1024;
+// for all the game's files together, the newest are kept
+const MAX_TOTAL_SIZE: u64 = 512 * 1024 * 1024;
+const MAX_APP_LOGS: usize = 3;
+
+const CRASH_FILE_EXTENSIONS: [&str; 4] = ["dmp", "mdmp", "log", "txt"];
+
+struct CrashFile {
+    name: String,
+    path: PathBuf,
+    modified: SystemTime,
+    size: u64,
+}
+
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Summary {
+    generated_at: String,
+    app_version: String,
+    os: &'static str,
+    arch: &'static str,
+    game_id: SupportedGames,
+    profile_id: Option<uuid::Uuid>,
+    files: Vec<String>,
+    skipped: Vec<String>, // files found but left out, with the reason
+}
+
+/// Bundle everything useful for a bug report into a single zip and return its
+/// path: the game's crash dumps and logs, the profile's mod list script, our own
+/// logs and the export of the profile that was played (the last session's one
+/// when `profile_id` is not given).
+pub fn collect(
+    app_handle: &tauri::AppHandle,
+    game_id: SupportedGames,
+    profile_id: Option<uuid::Uuid>,
+) -> Result<PathBuf, Box<dyn Error>> {
+    let game_info = DefaultGameInfo::find_by_id(game_id).ok_or("Couldn't find game preset")?;
+    let steam_config = SteamConfig::from_app_handle(app_handle)
+        .map_err(|e| format!("Failed to read Steam config: {:?}", e))?;
+    let game = GameStore::from_entries(
+        GameStore::get_store(app_handle, game_id)
+            .map_err(|e| format!("Failed to open game store: {:?}", e))?
+            .entries(),
+    )
+    .map_err(|e| format!("Failed to read game store: {:?}", e))?;
+
+    let profile_id = profile_id
+        .or_else(|| game.play_sessions.last().map(|s| s.profile_id))
+        .or(game.default_profile);
+
+    let output_dir = app_handle.path().app_data_dir()?.join("diagnostics");
+    std::fs::create_dir_all(&output_dir)?;
+    let output_path = output_dir.join(format!(
+        "foolhammer-diagnostics-{}.zip",
+        chrono::Utc::now().format("%Y%m%dT%H%M%S")
+    ));
+
+    let mut files: Vec<(String, PathBuf)> = vec![];
+    let mut skipped: Vec<String> = vec![];
+
+    match retrieve_saves_absolute_path(game_id, game_info.app_data_path, &steam_config) {
+        Some(app_data) if app_data.exists() => {
+            let mut crash_files = vec![];
+            collect_crash_files(&app_data, &app_data, &mut crash_files, &mut skipped);
+            files.extend(newest_within_limit(crash_files, &mut skipped));
+        }
+        _ => skipped.push(String::from("game AppData folder: not found")),
+    }
+
+    // the mod list script written for the profile's last launch
+    if let Some(script) = profile_id.map(writer::script_file_name) {
+        let path = game.game_path.join(&script);
+        if path.is_file() {
+            files.push((script, path));
+        } else {
+            skipped.push(format!("{}: not found", script));
+        }
+    }
+
+    match app_handle.path().app_log_dir() {
+        Ok(log_dir) => collect_app_logs(&log_dir, &mut files),
+        Err(e) => skipped.push(format!("foolhammer logs: {}", e)),
+    }
+
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut zip = ZipWriter::new(File::create(&output_path)?);
+
+    for (name, path) in &files {
+        zip.start_file(name.as_str(), options)?;
+        std::io::copy(&mut File::open(path)?, &mut zip)?;
+    }
+
+    let mut written: Vec<String> = files.into_iter().map(|(name, _)| name).collect();
+
+    if let Some(profile) = profile_id.and_then(|id| game.profiles.iter().find(|p| p.id == id)) {
+        let workshop_ids: HashMap<String, u64> = steam_config
+            .retrieve_steam_workshop_path(game_id)
+            .map(|path| ModPack::scan_workshop_ids(&path).into_iter().collect())
+            .unwrap_or_default();
+        let export = ProfileExport::new(&game, profile, &workshop_ids);
+
+        zip.start_file("profile.json", options)?;
+        zip.write_all(&serde_json::to_vec_pretty(&export)?)?;
+        written.push(String::from("profile.json"));
+    } else {
+        skipped.push(format!(
+            "profile export: profile {:?} not found",
+            profile_id
+        ));
+    }
+
+    let summary = Summary {
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        app_version: app_handle.package_info().version.to_string(),
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+        game_id,
+        profile_id,
+        files: written,
+        skipped,
+    };
+
+    zip.start_file("summary.json", options)?;
+    zip.write_all(&serde_json::to_vec_pretty(&summary)?)?;
+    zip.finish()?;
+
+    log::info!(
+        "Diagnostics written to {} ({} file(s), {} skipped)",
+        output_path.display(),
+        summary.files.len(),
+        summary.skipped.len()
+    );
+
+    Ok(output_path)
+}
+
+// crash dumps and logs from the game's AppData folder, saves excluded
+fn collect_crash_files(
+    root: &Path,
+    dir: &Path,
+    files: &mut Vec<CrashFile>,
+    skipped: &mut Vec<String>,
+) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        log::warn!("Failed to read {}", dir.display());
+        return;
+    };
+
+    for path in entries.filter_map(|e| e.ok().map(|e| e.path())) {
+        let Ok(relative) = path.strip_prefix(root) else {
+            continue;
+        };
+        let name = format!("game/{}", relative.to_string_lossy().replace('\\', "/"));
+
+        if path.is_dir() {
+            if path.file_name().is_some_and(|n| n == "save_games") {
+                continue;
+            }
+            collect_crash_files(root, &path, files, skipped);
+            continue;
+        }
+
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if !CRASH_FILE_EXTENSIONS.contains(&extension.as_str()) {
+            continue;
+        }
+
+        let Ok(metadata) = path.metadata() else {
+            continue;
+        };
+
+        let Ok(modified) = metadata.modified() else {
+            continue;
+        };
+
+        let recent = SystemTime::now()
+            .duration_since(modified)
+            .is_ok_and(|age| age <= MAX_FILE_AGE);
+        if !recent {
+            continue;
+        }
+
+        if metadata.len() > MAX_FILE_SIZE {
+            skipped.push(format!("{}: too large ({} bytes)", name, metadata.len()));
+            continue;
+        }
+
+        files.push(CrashFile {
+            name,
+            path,
+            modified,
+            size: metadata.len(),
+        });
+    }
+}
+
+fn newest_within_limit(
+    mut crash_files: Vec<CrashFile>,
+    skipped: &mut Vec<String>,
+) -> Vec<(String, PathBuf)> {
+    crash_files.sort_by(|a, b| b.modified.cmp(&a.modified));
+
+    let mut total: u64 = 0;
+    let mut files = vec![];
+
+    for file in crash_files {
+        if total + file.size > MAX_TOTAL_SIZE {
+            skipped.push(format!("{}: archive size limit reached", file.name));
+            continue;
+        }
+
+        total += file.size;
+        files.push((file.name, file.path));
+    }
+
+    files
+}
+
+// the latest rotated log files
+fn collect_app_logs(log_dir: &Path, files: &mut Vec<(String, PathBuf)>) {
+    let Ok(entries) = std::fs::read_dir(log_dir) else {
+        return;
+    };
+
+    let mut logs: Vec<(SystemTime, PathBuf)> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "log"))
+        .filter_map(|path| Some((path.metadata().ok()?.modified().ok()?, path)))
+        .collect();
+    logs.sort_by(|a, b| b.0.cmp(&a.0));
+
+    for (_, path) in logs.into_iter().take(MAX_APP_LOGS) {
+        let name = format!(
+            "foolhammer/{}",
+            path.file_name().unwrap_or_default().to_string_lossy()
+        );
+        files.push((name, path));
+    }
+}
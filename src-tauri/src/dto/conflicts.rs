@@ -0,0 +1,27 @@
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConflictReportDto {
+    pub profile_id: uuid::Uuid,
+    pub packs: Vec<String>,      // analyzed packs, highest priority first
+    pub unreadable: Vec<String>, // enabled packs rpfm_lib couldn't open
+    pub files: Vec<FileConflictDto>,
+    pub tables: Vec<TableConflictDto>,
+    pub tables_analyzed: bool, // false when the table schema couldn't be loaded
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileConflictDto {
+    pub path: String,
+    pub winner: String,
+    pub overridden: Vec<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableConflictDto {
+    pub table: String,
+    pub keys: Vec<String>, // key column values of the rows, joined with '|'
+    pub winner: String,
+    pub overridden: Vec<String>,
+}
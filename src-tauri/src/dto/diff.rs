@@ -0,0 +1,24 @@
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileDiffDto {
+    pub only_in_a: Vec<String>,
+    pub only_in_b: Vec<String>,
+    pub enabled_changes: Vec<EnabledChangeDto>,
+    pub order_changes: Vec<OrderChangeDto>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnabledChangeDto {
+    pub name: String,
+    pub enabled_a: bool,
+    pub enabled_b: bool,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderChangeDto {
+    pub name: String,
+    pub position_a: usize, // 1-based position among the mods both profiles share
+    pub position_b: usize,
+}
@@ -1,11 +1,12 @@
-use std::path::{Path, PathBuf};
+use std::{collections::HashMap, path::PathBuf};
 
 use crate::{
     dto::{
-        groups::GroupResponseDto, packs::PackResponseDto, profiles::ProfileResponseDto,
-        saves::SaveResponseDto,
+        groups::GroupResponseDto, masterlists::MasterlistDto, packs::PackResponseDto,
+        profiles::ProfileResponseDto, saves::SaveResponseDto,
     },
-    mods::pack,
+    launchers::{hooks::LaunchHook, runners::RunnerSelection},
+    mods::{pack, rules::LoadOrderRule},
     resolve_existing_path,
     stores::games,
     supported_games::SupportedGames,
@@ -33,6 +34,11 @@ pub struct GameResponseDto {
     pub saves_path: Option<PathBuf>,
     pub mods_path: PathBuf,
     pub workshop_path: Option<PathBuf>,
+    pub runner: RunnerSelection,
+    pub profile_runners: HashMap<uuid::Uuid, RunnerSelection>,
+    pub hooks: Vec<LaunchHook>,
+    pub load_order_rules: Vec<LoadOrderRule>,
+    pub masterlist: Option<MasterlistDto>,
 }
 
 impl GameResponseDto {
@@ -57,15 +63,31 @@ impl GameResponseDto {
             None => vec![],
         };
 
+        // Workshop items in the masterlist are matched through the installed packs
+        let masterlist = store.masterlist.map(|masterlist| {
+            let workshop_ids: Vec<(String, u64)> = mods
+                .iter()
+                .filter_map(|m| Some((m.name.clone(), m.workshop_id()?)))
+                .collect();
+            MasterlistDto::new(masterlist, &workshop_ids)
+        });
+
+        let rules: Vec<LoadOrderRule> = store
+            .load_order_rules
+            .iter()
+            .chain(masterlist.iter().flat_map(|m| &m.rules))
+            .cloned()
+            .collect();
+
         let custom_names = store.mod_custom_names.clone();
         let profiles: Vec<ProfileResponseDto> = store
             .profiles
             .into_iter()
-            .map(|profile| ProfileResponseDto::new(profile, &mods, &custom_names))
+            .map(|profile| ProfileResponseDto::new(profile, &mods, &custom_names, &rules))
             .collect();
 
         let saves = match &store.saves_path {
-            Some(path) => Self::get_saves(path),
+            Some(path) => SaveResponseDto::list(path, &store.save_links),
             None => {
                 log::debug!("No saves_path configured for game {:?}", store.game_id);
                 vec![]
@@ -78,6 +100,11 @@ impl GameResponseDto {
             saves_path: store.saves_path,
             mods_path: store.mods_path,
             workshop_path,
+            runner: store.runner,
+            profile_runners: store.profile_runners,
+            hooks: store.hooks,
+            load_order_rules: store.load_order_rules,
+            masterlist,
             saves,
             mods: Self::mods_to_dto(&mods),
             default_profile: store
@@ -92,23 +119,6 @@ impl GameResponseDto {
         }
     }
 
-    fn get_saves(folder_path: &Path) -> Vec<SaveResponseDto> {
-        let Ok(paths) = std::fs::read_dir(folder_path) else {
-            log::warn!("Failed to read saves directory: {}", folder_path.display());
-            return vec![];
-        };
-
-        if let Ok(saves) = paths
-            .map(|res| res.map(|e| e.path()))
-            .collect::<Result<Vec<_>, std::io::Error>>()
-        {
-            log::debug!("Found {} save(s) in {}", saves.len(), folder_path.display());
-            return saves.into_iter().map(SaveResponseDto::new).collect();
-        }
-
-        vec![]
-    }
-
     fn mods_to_dto(mods: &[pack::ModPack]) -> Vec<PackResponseDto> {
         mods.iter()
             .map(|pack| {
@@ -124,3 +134,14 @@ impl GameResponseDto {
             .collect()
     }
 }
+
+/// Payload of the `game/started`, `game/exited` and `game/crashed` events.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameProcessEventDto {
+    pub game_id: SupportedGames,
+    pub launch_id: uuid::Uuid,
+    pub pid: Option<u32>,
+    pub exit_code: Option<i32>, // only known on Linux, where the launcher waits for the game
+    pub diagnostics_path: Option<PathBuf>, // zip collected after a crash
+}
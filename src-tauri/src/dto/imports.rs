@@ -0,0 +1,16 @@
+use crate::{mods::share::ProfileExport, stores::games::Profile};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileImportResponseDto {
+    pub profile: Profile,
+    pub missing: Vec<String>, // imported mods with no matching pack on disk
+    pub missing_workshop_ids: Vec<u64>, // referenced workshop items that aren't subscribed
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileExportResponseDto {
+    pub export: ProfileExport,
+    pub share_code: String,
+}
@@ -0,0 +1,28 @@
+use std::path::PathBuf;
+
+use crate::launchers::runners::UmuSource;
+
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UmuStatusDto {
+    pub source: UmuSource,
+    pub installed_tag: Option<String>,
+    pub installed_release_date: Option<String>,
+    pub runner_path: Option<PathBuf>, // None when no usable umu-run was found
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RunnerKind {
+    Proton,
+    Wine,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunnerDto {
+    pub name: String,
+    pub path: PathBuf,
+    pub kind: RunnerKind,
+}
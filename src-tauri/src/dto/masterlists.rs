@@ -0,0 +1,29 @@
+use crate::mods::{
+    masterlist::{Masterlist, MasterlistRequirement, MasterlistSource},
+    rules::LoadOrderRule,
+};
+
+/// The game's masterlist, applied to the packs currently installed.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MasterlistDto {
+    pub source: MasterlistSource,
+    pub name: Option<String>,
+    pub imported_at: String,
+    pub entries: usize,
+    pub rules: Vec<LoadOrderRule>,
+    pub requirements: Vec<MasterlistRequirement>,
+}
+
+impl MasterlistDto {
+    pub fn new(masterlist: Masterlist, workshop_ids: &[(String, u64)]) -> Self {
+        Self {
+            rules: masterlist.rules(workshop_ids),
+            requirements: masterlist.requirements(workshop_ids),
+            entries: masterlist.entries.len(),
+            source: masterlist.source,
+            name: masterlist.name,
+            imported_at: masterlist.imported_at,
+        }
+    }
+}
@@ -1,7 +1,14 @@
+pub mod conflicts;
+pub mod diff;
 pub mod games;
 pub mod groups;
+pub mod imports;
+pub mod launchers;
+pub mod masterlists;
 pub mod mods;
 pub mod packs;
 pub mod profiles;
 pub mod saves;
+pub mod sessions;
 pub mod settings;
+pub mod validation;
@@ -1,6 +1,13 @@
+use std::collections::HashMap;
+
 use crate::{
     dto::mods::{ModRequestDto, ModResponseDto},
-    mods::{pack::ModPack, sort::SortMods},
+    launchers::options::LaunchOptions,
+    mods::{
+        pack::ModPack,
+        rules::{self, LoadOrderRule},
+        sort,
+    },
     stores::games,
     supported_games::SupportedGames,
 };
@@ -25,15 +32,24 @@ pub struct ProfileResponseDto {
     pub mods: Vec<ModResponseDto>,
     pub manual_mode: bool,
     pub groups: Vec<uuid::Uuid>,
+    pub dependency_cycle: Option<Vec<String>>, // set when the automatic order couldn't satisfy every dependency
+    pub launch_options: LaunchOptions,
 }
 
 impl ProfileResponseDto {
     pub fn new(
         mut profile: games::Profile,
         game_mods: &[ModPack],
-        custom_names: &std::collections::HashMap<String, String>,
+        custom_names: &HashMap<String, String>,
+        rules: &[LoadOrderRule],
     ) -> Self {
-        let mods = Self::map_mods_to_dto(&mut profile, game_mods, custom_names);
+        let dependency_cycle = if profile.manual_mode {
+            None
+        } else {
+            Self::auto_sort(&mut profile, game_mods, rules).err()
+        };
+
+        let mods = Self::map_mods_to_dto(&profile, game_mods, custom_names);
 
         Self {
             id: profile.id,
@@ -41,24 +57,72 @@ impl ProfileResponseDto {
             manual_mode: profile.manual_mode,
             mods,
             groups: profile.groups,
+            dependency_cycle,
+            launch_options: profile.launch_options,
         }
     }
 
-    fn map_mods_to_dto(
+    /// The profile's mods in the order the game loads them: as stored in manual
+    /// mode, sorted the way `new` sorts them otherwise.
+    pub fn sorted_mods(
+        mut profile: games::Profile,
+        game_mods: &[ModPack],
+        rules: &[LoadOrderRule],
+    ) -> Vec<games::ProfileModInfo> {
+        if !profile.manual_mode {
+            let _ = Self::auto_sort(&mut profile, game_mods, rules);
+        }
+
+        profile.mods
+    }
+
+    /// Dependencies and the game's load order rules first, name order as the
+    /// tie-breaker. The mods are renumbered even when a cycle is found so the
+    /// profile keeps a consistent order.
+    fn auto_sort(
         profile: &mut games::Profile,
         mods: &[ModPack],
-        custom_names: &std::collections::HashMap<String, String>,
-    ) -> Vec<ModResponseDto> {
-        if !profile.manual_mode {
-            profile.mods.sort_mods(|m| &m.name);
+        rules: &[LoadOrderRule],
+    ) -> Result<(), Vec<String>> {
+        let packs: HashMap<&str, &ModPack> = mods.iter().map(|m| (m.name.as_str(), m)).collect();
+        let names: Vec<&str> = profile.mods.iter().map(|m| m.name.as_str()).collect();
+        let rule_deps = rules::load_after_map(rules, &names);
+
+        let result = sort::sort_by_dependencies(
+            &mut profile.mods,
+            |m| &m.name,
+            |m| {
+                let mut deps = packs
+                    .get(m.name.as_str())
+                    .map(|pack| pack.dependency_names())
+                    .unwrap_or_default();
+                deps.extend(rule_deps.get(&m.name).cloned().unwrap_or_default());
+                deps
+            },
+        );
+
+        if let Err(cycle) = &result {
+            log::error!(
+                "Dependency cycle in profile '{}': {}",
+                profile.name,
+                cycle.join(" -> ")
+            );
+        }
 
-            for i in 0..profile.mods.len() {
-                profile.mods[i].order = (u32::try_from(i)
-                    .expect("u32 overflow, it wasn't possible to convert usize to u32"))
-                    + 1;
-            }
+        for i in 0..profile.mods.len() {
+            profile.mods[i].order = (u32::try_from(i)
+                .expect("u32 overflow, it wasn't possible to convert usize to u32"))
+                + 1;
         }
 
+        result
+    }
+
+    fn map_mods_to_dto(
+        profile: &games::Profile,
+        mods: &[ModPack],
+        custom_names: &HashMap<String, String>,
+    ) -> Vec<ModResponseDto> {
         profile
             .mods
             .iter()
@@ -1,4 +1,31 @@
-use std::path::PathBuf;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use crate::{dto::profiles::ProfileResponseDto, saves::links, stores::games::SaveLink};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SaveKind {
+    Manual,
+    Autosave,
+    Quicksave,
+}
+
+impl SaveKind {
+    // the game names its own saves `autosave*` and `quicksave*`
+    fn from_name(name: &str) -> Self {
+        let name = name.to_lowercase();
+        if name.starts_with("autosave") {
+            Self::Autosave
+        } else if name.starts_with("quicksave") {
+            Self::Quicksave
+        } else {
+            Self::Manual
+        }
+    }
+}
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -7,10 +34,14 @@ pub struct SaveResponseDto {
     pub path: PathBuf,
     pub last_updated: Option<String>,
     pub last_accessed: Option<String>,
+    pub size: u64,
+    pub kind: SaveKind,
+    pub profile_id: Option<uuid::Uuid>, // profile active when the save was last written
+    pub mods_hash: Option<String>,
 }
 
 impl SaveResponseDto {
-    pub fn new(path: PathBuf) -> Self {
+    pub fn new(path: PathBuf, link: Option<&SaveLink>) -> Self {
         let meta = std::fs::metadata(&path).expect("Failed to retrieve metadata for save file. This should not happen as the file was just read from the directory.");
         let name = path
             .file_name()
@@ -27,10 +58,151 @@ impl SaveResponseDto {
             .map(|t| (chrono::DateTime::<chrono::Utc>::from(t)).to_rfc3339());
 
         Self {
+            kind: SaveKind::from_name(&name),
             name,
             path,
             last_updated,
             last_accessed,
+            size: meta.len(),
+            profile_id: link.map(|l| l.profile_id),
+            mods_hash: link.map(|l| l.mods_hash.clone()),
         }
     }
+
+    /// Every save in the folder, with the profile links recorded for them.
+    pub fn list(folder_path: &Path, save_links: &HashMap<String, SaveLink>) -> Vec<Self> {
+        let Ok(paths) = std::fs::read_dir(folder_path) else {
+            log::warn!("Failed to read saves directory: {}", folder_path.display());
+            return vec![];
+        };
+
+        let saves: Vec<Self> = paths
+            .filter_map(|res| res.ok().map(|e| e.path()))
+            .filter(|path| path.is_file() && links::is_save_file(path))
+            .map(|path| {
+                let link = path
+                    .file_name()
+                    .and_then(|name| save_links.get(name.to_string_lossy().as_ref()));
+                Self::new(path, link)
+            })
+            .collect();
+
+        log::debug!("Found {} save(s) in {}", saves.len(), folder_path.display());
+
+        saves
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SaveSortKey {
+    Name,
+    #[default]
+    LastUpdated,
+    Size,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SaveQueryDto {
+    pub sort_by: SaveSortKey,
+    pub ascending: bool, // newest, largest or last name first unless set
+    pub kinds: Option<Vec<SaveKind>>,
+    pub search: Option<String>, // case-insensitive match on the file name
+    pub profile_id: Option<uuid::Uuid>,
+}
+
+impl SaveQueryDto {
+    pub fn apply(&self, saves: Vec<SaveResponseDto>) -> Vec<SaveResponseDto> {
+        let search = self.search.as_ref().map(|s| s.trim().to_lowercase());
+
+        let mut saves: Vec<SaveResponseDto> = saves
+            .into_iter()
+            .filter(|s| {
+                self.kinds
+                    .as_ref()
+                    .is_none_or(|kinds| kinds.contains(&s.kind))
+            })
+            .filter(|s| {
+                search
+                    .as_ref()
+                    .is_none_or(|search| s.name.to_lowercase().contains(search))
+            })
+            .filter(|s| self.profile_id.is_none() || s.profile_id == self.profile_id)
+            .collect();
+
+        saves.sort_by(|a, b| {
+            let ordering = match self.sort_by {
+                SaveSortKey::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+                SaveSortKey::LastUpdated => a.last_updated.cmp(&b.last_updated),
+                SaveSortKey::Size => a.size.cmp(&b.size),
+            };
+
+            if self.ascending {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        });
+
+        saves
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveCompatibilityDto {
+    pub save_name: String,
+    pub linked_profile_id: Option<uuid::Uuid>,
+    pub mods_match: Option<bool>, // None when the save isn't linked to a mod list
+    pub suggested_profile_id: Option<uuid::Uuid>, // a profile currently loading the save's mod list
+}
+
+impl SaveCompatibilityDto {
+    /// How the save's mod list compares with the one `profile_id` loads. `None`
+    /// when the profile doesn't exist.
+    pub fn new(
+        save: &SaveResponseDto,
+        profiles: &[ProfileResponseDto],
+        profile_id: uuid::Uuid,
+    ) -> Option<Self> {
+        let hashes: Vec<(uuid::Uuid, String)> = profiles
+            .iter()
+            .map(|p| (p.id, links::mod_list_hash(&p.mods)))
+            .collect();
+
+        let current_hash = hashes
+            .iter()
+            .find(|(id, _)| *id == profile_id)
+            .map(|(_, hash)| hash)?;
+
+        let mods_match = save.mods_hash.as_ref().map(|hash| hash == current_hash);
+
+        // prefer the profile that wrote the save, if it still loads the same mods
+        let suggested_profile_id = save.mods_hash.as_ref().and_then(|save_hash| {
+            let mut matching = hashes.iter().filter(|(_, hash)| hash == save_hash);
+            matching
+                .clone()
+                .find(|(id, _)| Some(*id) == save.profile_id)
+                .or_else(|| matching.next())
+                .map(|(id, _)| *id)
+        });
+
+        Some(Self {
+            save_name: save.name.clone(),
+            linked_profile_id: save.profile_id,
+            mods_match,
+            suggested_profile_id,
+        })
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveBackupDto {
+    pub id: String, // `<campaign>/<file>`, relative to the game's backup folder
+    pub campaign: String,
+    pub save_name: String,
+    pub created_at: Option<String>,
+    pub size: u64,
 }
@@ -0,0 +1,58 @@
+use crate::stores::games::{GameStore, PlaySession};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayHistoryDto {
+    pub profiles: Vec<ProfilePlaytimeDto>, // most played first
+    pub recent_sessions: Vec<PlaySession>, // newest first
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfilePlaytimeDto {
+    pub profile_id: uuid::Uuid,
+    pub profile_name: Option<String>, // None once the profile is deleted
+    pub sessions: usize,
+    pub crashes: usize,
+    pub total_secs: u64,
+    pub last_played: Option<String>,
+}
+
+impl PlayHistoryDto {
+    pub fn new(game: &GameStore, recent: usize) -> Self {
+        let mut profiles: Vec<ProfilePlaytimeDto> = game
+            .play_totals
+            .iter()
+            .map(|(profile_id, totals)| ProfilePlaytimeDto {
+                profile_id: *profile_id,
+                profile_name: game
+                    .profiles
+                    .iter()
+                    .find(|p| &p.id == profile_id)
+                    .map(|p| p.name.clone()),
+                sessions: totals.sessions,
+                crashes: totals.crashes,
+                total_secs: totals.total_secs,
+                last_played: totals.last_played.clone(),
+            })
+            .collect();
+        profiles.sort_by(|a, b| {
+            b.total_secs
+                .cmp(&a.total_secs)
+                .then_with(|| a.profile_id.cmp(&b.profile_id))
+        });
+
+        let recent_sessions = game
+            .play_sessions
+            .iter()
+            .rev()
+            .take(recent)
+            .cloned()
+            .collect();
+
+        Self {
+            profiles,
+            recent_sessions,
+        }
+    }
+}
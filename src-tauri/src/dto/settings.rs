@@ -1,6 +1,10 @@
 use std::path::PathBuf;
 
-use crate::{stores::settings::SettingsStore, supported_games::SupportedGames};
+use crate::{
+    launchers::runners::UmuSource, mods::validation::DependencyPolicy,
+    saves::backups::SaveBackupSettings, stores::settings::SettingsStore,
+    supported_games::SupportedGames,
+};
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -9,6 +13,9 @@ pub struct UserSettingsResponseDto {
     pub steam_path: Option<PathBuf>,
     pub steam_library_path: Option<PathBuf>,
     pub invert_mod_names: bool,
+    pub dependency_policy: DependencyPolicy,
+    pub save_backups: SaveBackupSettings,
+    pub umu_source: UmuSource,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -25,6 +32,9 @@ impl From<&SettingsStore> for UserSettingsResponseDto {
             steam_path: settings.steam_path.clone(),
             steam_library_path: settings.steam_library_path.clone(),
             invert_mod_names: settings.invert_mod_names,
+            dependency_policy: settings.dependency_policy,
+            save_backups: settings.save_backups,
+            umu_source: settings.umu_source.clone(),
         }
     }
 }
@@ -0,0 +1,17 @@
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ValidationProblemKind {
+    MissingDependency,     // the dependency isn't on disk
+    DisabledDependency,    // the dependency is on disk but not enabled in the profile
+    DependencyLoadedAfter, // the dependency is enabled but comes after the mod needing it
+    Incompatible,          // a load order rule says the two enabled mods don't work together
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationProblemDto {
+    pub kind: ValidationProblemKind,
+    pub mod_name: String,
+    pub target: String, // the other pack involved
+    pub hard: bool,     // hard dependency as flagged in the pack, always set for incompatibilities
+}
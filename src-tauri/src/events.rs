@@ -1,6 +1,11 @@
 pub enum AppEvent {
     UpdateUserSettings,
     RefreshGame,
+    ScanProgress,
+    GameStarted,
+    GameExited,
+    GameCrashed,
+    SaveModsMismatch,
 }
 
 impl From<AppEvent> for &'static str {
@@ -8,6 +13,11 @@ impl From<AppEvent> for &'static str {
         match event {
             AppEvent::UpdateUserSettings => "update_user_settings",
             AppEvent::RefreshGame => "refresh_game",
+            AppEvent::ScanProgress => "scan/progress",
+            AppEvent::GameStarted => "game/started",
+            AppEvent::GameExited => "game/exited",
+            AppEvent::GameCrashed => "game/crashed",
+            AppEvent::SaveModsMismatch => "save/mods_mismatch",
         }
     }
 }
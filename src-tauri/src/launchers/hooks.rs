@@ -0,0 +1,263 @@
+#[cfg(unix)]
+use std::os::unix::process::CommandExt;
+use std::{
+    io::{BufRead, BufReader, Read},
+    path::PathBuf,
+    process::{Child, Command, Stdio},
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{launchers::runners, stores::games::SessionOutcome, supported_games::SupportedGames};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// When a hook runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HookStage {
+    PreLaunch,
+    PostExit,
+}
+
+/// What a failed hook (error, non-zero exit or timeout) does to the launch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HookFailurePolicy {
+    /// Don't start the game. Same as `Warn` after the game exited.
+    Abort,
+    #[default]
+    Warn,
+    Ignore,
+}
+
+/// A command run around play sessions, configured per game.
+///
+/// Hooks get `FOOLHAMMER_GAME_ID`, `FOOLHAMMER_PROFILE_ID`, `FOOLHAMMER_GAME_PATH`,
+/// `FOOLHAMMER_MOD_LIST_FILE` and `FOOLHAMMER_MODS` (the loaded packs in order,
+/// one per line) in their environment. Post-exit hooks also get
+/// `FOOLHAMMER_OUTCOME` and, when known, `FOOLHAMMER_EXIT_CODE`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchHook {
+    pub name: String,
+    pub stage: HookStage,
+    pub program: PathBuf,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    #[serde(default)]
+    pub on_failure: HookFailurePolicy,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_timeout_secs() -> u64 {
+    60
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl LaunchHook {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err(String::from("hook name is empty"));
+        }
+
+        if self.timeout_secs == 0 {
+            return Err(format!("hook '{}' has no timeout", self.name));
+        }
+
+        let found = if self.program.components().count() > 1 {
+            self.program.is_file()
+        } else {
+            runners::find_in_path(&self.program.to_string_lossy()).is_some()
+        };
+        if !found {
+            return Err(format!(
+                "program {} of hook '{}' not found",
+                self.program.display(),
+                self.name
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// The launch the hooks run around.
+#[derive(Debug, Clone)]
+pub struct HookContext {
+    pub game_id: SupportedGames,
+    pub profile_id: uuid::Uuid,
+    pub game_path: PathBuf,
+    pub mod_list_file: PathBuf,
+    pub mods: Vec<String>,
+}
+
+/// The game's hooks for one launch. Post-exit hooks are run by the process
+/// monitor once the game is gone, or by `start_game` when the launch fails
+/// after the pre-launch hooks ran.
+#[derive(Debug, Clone)]
+pub struct LaunchHooks {
+    hooks: Vec<LaunchHook>,
+    context: HookContext,
+}
+
+impl LaunchHooks {
+    pub fn new(hooks: Vec<LaunchHook>, context: HookContext) -> Self {
+        Self {
+            hooks: hooks.into_iter().filter(|h| h.enabled).collect(),
+            context,
+        }
+    }
+
+    /// Run the pre-launch hooks in order. Fails on the first failed hook whose
+    /// policy is `Abort`; the remaining hooks are not run.
+    pub fn pre_launch(&self) -> Result<(), String> {
+        for hook in self.stage(HookStage::PreLaunch) {
+            if let Err(e) = self.run(hook, &[]) {
+                match hook.on_failure {
+                    HookFailurePolicy::Abort => {
+                        log::error!("Hook '{}' failed, aborting the launch: {}", hook.name, e);
+                        return Err(format!("hook '{}' failed: {}", hook.name, e));
+                    }
+                    HookFailurePolicy::Warn => log::warn!("Hook '{}' failed: {}", hook.name, e),
+                    HookFailurePolicy::Ignore => log::debug!("Hook '{}' failed: {}", hook.name, e),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn post_exit(&self, outcome: SessionOutcome, exit_code: Option<i32>) {
+        let outcome = serde_json::to_value(outcome)
+            .ok()
+            .and_then(|v| v.as_str().map(String::from))
+            .unwrap_or_default();
+
+        let mut env = vec![(String::from("FOOLHAMMER_OUTCOME"), outcome)];
+        if let Some(code) = exit_code {
+            env.push((String::from("FOOLHAMMER_EXIT_CODE"), code.to_string()));
+        }
+
+        for hook in self.stage(HookStage::PostExit) {
+            if let Err(e) = self.run(hook, &env) {
+                match hook.on_failure {
+                    HookFailurePolicy::Abort | HookFailurePolicy::Warn => {
+                        log::warn!("Hook '{}' failed: {}", hook.name, e)
+                    }
+                    HookFailurePolicy::Ignore => log::debug!("Hook '{}' failed: {}", hook.name, e),
+                }
+            }
+        }
+    }
+
+    fn stage(&self, stage: HookStage) -> impl Iterator<Item = &LaunchHook> {
+        self.hooks.iter().filter(move |h| h.stage == stage)
+    }
+
+    fn run(&self, hook: &LaunchHook, extra_env: &[(String, String)]) -> Result<(), String> {
+        log::info!(
+            "Running {:?} hook '{}': {} {:?}",
+            hook.stage,
+            hook.name,
+            hook.program.display(),
+            hook.args
+        );
+
+        let game_id: String = self.context.game_id.into();
+        let mut command = Command::new(&hook.program);
+        command
+            .args(&hook.args)
+            .current_dir(&self.context.game_path)
+            .env("FOOLHAMMER_GAME_ID", game_id)
+            .env("FOOLHAMMER_PROFILE_ID", self.context.profile_id.to_string())
+            .env("FOOLHAMMER_GAME_PATH", &self.context.game_path)
+            .env("FOOLHAMMER_MOD_LIST_FILE", &self.context.mod_list_file)
+            .env("FOOLHAMMER_MODS", self.context.mods.join("\n"))
+            .envs(extra_env.iter().map(|(k, v)| (k, v)))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        // in a group of its own, so a timeout also kills what the hook started
+        #[cfg(unix)]
+        command.process_group(0);
+
+        let mut child = command
+            .spawn()
+            .map_err(|e| format!("failed to start: {}", e))?;
+
+        // read both pipes as the hook runs, a full pipe would block it. The
+        // readers aren't joined: a process the hook left running can keep them open.
+        if let Some(stdout) = child.stdout.take() {
+            log_output(&hook.name, stdout, false);
+        }
+        if let Some(stderr) = child.stderr.take() {
+            log_output(&hook.name, stderr, true);
+        }
+
+        let deadline = Instant::now() + Duration::from_secs(hook.timeout_secs);
+        let status = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break Ok(status),
+                Ok(None) if Instant::now() >= deadline => {
+                    kill_tree(&mut child);
+                    let _ = child.wait();
+                    break Err(format!("timed out after {}s", hook.timeout_secs));
+                }
+                Ok(None) => std::thread::sleep(POLL_INTERVAL),
+                Err(e) => break Err(format!("failed to wait: {}", e)),
+            }
+        };
+
+        let status = status?;
+        if !status.success() {
+            return Err(format!("exited with {}", status));
+        }
+
+        log::info!("Hook '{}' finished", hook.name);
+
+        Ok(())
+    }
+}
+
+fn kill_tree(child: &mut Child) {
+    let pid = child.id().to_string();
+
+    #[cfg(unix)]
+    let result = Command::new("kill")
+        .args(["-KILL", "--", &format!("-{}", pid)])
+        .status();
+    #[cfg(windows)]
+    let result = Command::new("taskkill")
+        .args(["/T", "/F", "/PID", &pid])
+        .status();
+
+    if let Err(e) = result {
+        log::warn!("Failed to kill the processes of hook (pid={}): {}", pid, e);
+    }
+
+    // the group is gone by now, unless `kill` itself couldn't run
+    let _ = child.kill();
+}
+
+fn log_output(name: &str, output: impl Read + Send + 'static, stderr: bool) {
+    let name = name.to_string();
+
+    std::thread::spawn(move || {
+        for line in BufReader::new(output).lines().map_while(Result::ok) {
+            if stderr {
+                log::warn!("[hook {}] {}", name, line);
+            } else {
+                log::info!("[hook {}] {}", name, line);
+            }
+        }
+    });
+}
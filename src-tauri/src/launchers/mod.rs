@@ -1,12 +1,21 @@
-use crate::supported_games::SupportedGames;
+use crate::{
+    launchers::{options::LaunchOptions, runners::RunnerSelection},
+    supported_games::SupportedGames,
+};
 use std::{
     error::Error,
     path::{Path, PathBuf},
-    process::Command,
+    process::{Child, Command},
 };
 
+pub(crate) mod hooks;
 #[cfg(target_os = "linux")]
 mod linux;
+pub(crate) mod monitor;
+pub(crate) mod options;
+pub(crate) mod runners;
+#[cfg(target_os = "linux")]
+pub(crate) mod umu;
 #[cfg(target_os = "windows")]
 mod windows;
 
@@ -16,7 +25,10 @@ pub trait GameManager: Send {
         game_id: SupportedGames,
         game_path: &Path,
         save_path: Option<&PathBuf>, // the absolute path conteining the savegame file too
-    ) -> Result<(), Box<dyn Error>>;
+        mod_list: &str,              // the profile's mod list script, relative to `game_path`
+        runner: &RunnerSelection,    // only used on Linux
+        launch_options: &LaunchOptions,
+    ) -> Result<Child, Box<dyn Error>>; // the launcher process, which may not be the game itself
     fn kill_game(&mut self) -> Result<(), Box<dyn Error>>;
     fn get_command(&mut self) -> &mut Command;
 }
@@ -27,8 +39,11 @@ pub(crate) type GameLauncher = linux::LinuxLauncher;
 pub(crate) type GameLauncher = windows::WindowsLauncher;
 
 impl GameLauncher {
-    pub(crate) async fn create(app_handle: &tauri::AppHandle) -> GameLauncher {
+    pub(crate) async fn create(
+        app_handle: &tauri::AppHandle,
+        runner: &RunnerSelection,
+    ) -> Result<GameLauncher, Box<dyn Error>> {
         log::info!("Initializing {} game launcher", std::env::consts::OS);
-        GameLauncher::new(app_handle).await
+        GameLauncher::new(app_handle, runner).await
     }
 }
@@ -0,0 +1,302 @@
+use std::{
+    process::{Child, ExitStatus},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use sysinfo::{Pid, ProcessesToUpdate, System};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{
+    diagnostics,
+    dto::games::GameProcessEventDto,
+    events::AppEvent,
+    launchers::hooks::LaunchHooks,
+    state::State,
+    stores::games::{GameStore, MAX_PLAY_SESSIONS, PlaySession, SessionOutcome},
+    supported_games::SupportedGames,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+// Proton may have to set up the prefix or Steam may be starting, so the game
+// process can take a while to show up.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(180);
+
+// How long to wait for the launcher to report the exit code once the game is gone.
+const LAUNCHER_EXIT_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Game processes started slightly before the launch timestamp (clock rounding)
+// still count as ours.
+const START_TIME_SLACK_SECS: u64 = 5;
+
+/// The game process started by `start_game`, as tracked by the monitor.
+#[derive(Debug, Clone)]
+pub struct GameProcess {
+    pub launch_id: uuid::Uuid,
+    pub game_id: SupportedGames,
+    pub profile_id: uuid::Uuid,
+    pub save_name: Option<String>,
+    pub pid: Option<u32>, // the game itself, once found
+}
+
+/// Follow a launched game on a background thread until it exits.
+///
+/// On Linux the game is started through umu and Proton, so it's looked up by
+/// executable name, preferring descendants of the launcher process; on Windows
+/// the launched process is the game itself. `game/started` is emitted once it's
+/// found, then either `game/exited` or `game/crashed` when it's gone, and the
+/// runner and session are cleared from the state. The post-exit hooks run last.
+///
+/// A crash is told apart by the launcher's exit code: the game's own on Windows,
+/// passed on by umu on Linux. A game that never started isn't a crash, it's
+/// reported as exited without a pid.
+pub(crate) fn watch(
+    app_handle: AppHandle,
+    process: GameProcess,
+    executable_name: &'static str,
+    mut launcher: Child,
+    hooks: LaunchHooks,
+) {
+    std::thread::spawn(move || {
+        let launcher_pid = Pid::from_u32(launcher.id());
+        let launched_at = Instant::now();
+        // moved to when the game process shows up, so startup isn't counted as playtime
+        let mut started_at = chrono::Utc::now();
+        let launch_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        let mut sys = System::new();
+        let mut game_pid: Option<Pid> = None;
+        // only the launcher's exit after the game started says how the game ended
+        let mut launcher_status: Option<ExitStatus> = None;
+        let mut launcher_exited_early = false;
+
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+
+            if launcher_status.is_none()
+                && let Ok(Some(status)) = launcher.try_wait()
+            {
+                launcher_status = Some(status);
+                launcher_exited_early = game_pid.is_none();
+            }
+
+            sys.refresh_processes(ProcessesToUpdate::All, true);
+
+            match game_pid {
+                None => {
+                    if let Some(pid) =
+                        find_game_process(&sys, executable_name, launcher_pid, launch_time)
+                    {
+                        log::info!("Game process found (pid={})", pid);
+                        game_pid = Some(pid);
+                        started_at = chrono::Utc::now();
+                        set_pid(&app_handle, process.launch_id, pid.as_u32());
+                        emit(
+                            &app_handle,
+                            AppEvent::GameStarted,
+                            event_payload(&process, Some(pid), None),
+                        );
+                        continue;
+                    }
+
+                    let launcher_failed = launcher_status.is_some_and(|s| !s.success());
+                    if launcher_failed || launched_at.elapsed() > STARTUP_TIMEOUT {
+                        log::error!(
+                            "Game process never started (launcher status: {:?})",
+                            launcher_status
+                        );
+                        break;
+                    }
+                }
+                Some(pid) => {
+                    if sys.process(pid).is_none() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        if launcher_status.is_none() {
+            let deadline = Instant::now() + LAUNCHER_EXIT_TIMEOUT;
+            while launcher_status.is_none() && Instant::now() < deadline {
+                launcher_status = launcher.try_wait().ok().flatten();
+                std::thread::sleep(Duration::from_millis(200));
+            }
+
+            if launcher_status.is_none() {
+                // reap it whenever it exits
+                std::thread::spawn(move || launcher.wait());
+            }
+        }
+
+        let exit_code = launcher_status
+            .filter(|_| !launcher_exited_early)
+            .and_then(|s| s.code());
+
+        // a game stopped through `stop_game` is no longer in the state, and
+        // being killed isn't a crash
+        let stopped = !clear_state(&app_handle, process.launch_id);
+        let crashed = !stopped && game_pid.is_some() && exit_code.is_some_and(|c| c != 0);
+
+        log::info!(
+            "Game process ended (pid={:?}, exit_code={:?}, crashed={}, stopped={})",
+            game_pid,
+            exit_code,
+            crashed,
+            stopped
+        );
+
+        let outcome = if stopped {
+            SessionOutcome::Stopped
+        } else if game_pid.is_none() {
+            SessionOutcome::NotStarted
+        } else if crashed {
+            SessionOutcome::Crashed
+        } else {
+            SessionOutcome::Exited
+        };
+        if outcome != SessionOutcome::NotStarted {
+            record_session(&app_handle, &process, started_at, outcome);
+        }
+
+        let mut payload = event_payload(&process, game_pid, exit_code);
+        let event = if crashed {
+            // collected right away, while this run's dumps and logs are the newest
+            payload.diagnostics_path =
+                diagnostics::collect(&app_handle, process.game_id, Some(process.profile_id))
+                    .inspect_err(|e| log::warn!("Failed to collect diagnostics: {}", e))
+                    .ok();
+            AppEvent::GameCrashed
+        } else {
+            AppEvent::GameExited
+        };
+        emit(&app_handle, event, payload);
+
+        hooks.post_exit(outcome, exit_code);
+    });
+}
+
+fn find_game_process(
+    sys: &System,
+    executable_name: &str,
+    launcher_pid: Pid,
+    launch_time: u64,
+) -> Option<Pid> {
+    let candidates: Vec<&sysinfo::Process> =
+        sys.processes_by_name(executable_name.as_ref()).collect();
+
+    candidates
+        .iter()
+        .find(|p| p.pid() == launcher_pid || is_descendant(sys, p.pid(), launcher_pid))
+        .or_else(|| {
+            candidates
+                .iter()
+                .find(|p| p.start_time() + START_TIME_SLACK_SECS >= launch_time)
+        })
+        .map(|p| p.pid())
+}
+
+fn is_descendant(sys: &System, pid: Pid, ancestor: Pid) -> bool {
+    let mut current = sys.process(pid).and_then(|p| p.parent());
+
+    // bounded, in case the process table changes under us and forms a loop
+    for _ in 0..64 {
+        match current {
+            Some(parent) if parent == ancestor => return true,
+            Some(parent) => current = sys.process(parent).and_then(|p| p.parent()),
+            None => return false,
+        }
+    }
+
+    false
+}
+
+fn set_pid(app_handle: &AppHandle, launch_id: uuid::Uuid, pid: u32) {
+    let state = app_handle.state::<tauri::async_runtime::Mutex<State>>();
+    let mut state = state.blocking_lock();
+
+    if let Some(process) = state
+        .game_process
+        .as_mut()
+        .filter(|p| p.launch_id == launch_id)
+    {
+        process.pid = Some(pid);
+    }
+}
+
+// Returns false when the state already moved on (stopped or relaunched).
+fn clear_state(app_handle: &AppHandle, launch_id: uuid::Uuid) -> bool {
+    let state = app_handle.state::<tauri::async_runtime::Mutex<State>>();
+    let mut state = state.blocking_lock();
+
+    if state.game_process.as_ref().map(|p| p.launch_id) != Some(launch_id) {
+        return false;
+    }
+
+    state.game_process = None;
+    state.game_runner = None;
+    state.active_session = None;
+
+    true
+}
+
+fn record_session(
+    app_handle: &AppHandle,
+    process: &GameProcess,
+    started_at: chrono::DateTime<chrono::Utc>,
+    outcome: SessionOutcome,
+) {
+    let ended_at = chrono::Utc::now();
+    let session = PlaySession {
+        id: uuid::Uuid::new_v4(),
+        profile_id: process.profile_id,
+        save_name: process.save_name.clone(),
+        started_at: started_at.to_rfc3339(),
+        ended_at: ended_at.to_rfc3339(),
+        duration_secs: u64::try_from((ended_at - started_at).num_seconds()).unwrap_or_default(),
+        outcome,
+    };
+
+    let result =
+        tauri::async_runtime::block_on(GameStore::get(app_handle, process.game_id, |game| {
+            game.play_totals
+                .entry(session.profile_id)
+                .or_default()
+                .add(&session);
+            game.play_sessions.push(session);
+
+            if game.play_sessions.len() > MAX_PLAY_SESSIONS {
+                let excess = game.play_sessions.len() - MAX_PLAY_SESSIONS;
+                game.play_sessions.drain(..excess);
+            }
+
+            Ok(())
+        }));
+
+    if let Err(e) = result {
+        log::warn!("Failed to record play session: {:?}", e);
+    }
+}
+
+fn event_payload(
+    process: &GameProcess,
+    pid: Option<Pid>,
+    exit_code: Option<i32>,
+) -> GameProcessEventDto {
+    GameProcessEventDto {
+        game_id: process.game_id,
+        launch_id: process.launch_id,
+        pid: pid.map(|p| p.as_u32()),
+        exit_code,
+        diagnostics_path: None,
+    }
+}
+
+fn emit(app_handle: &AppHandle, event: AppEvent, payload: GameProcessEventDto) {
+    if let Err(e) = app_handle.emit(event.into(), payload) {
+        log::warn!("Failed to emit game process event: {:?}", e);
+    }
+}
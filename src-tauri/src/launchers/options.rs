@@ -0,0 +1,121 @@
+use std::{collections::BTreeMap, path::Path, process::Command};
+
+use serde::{Deserialize, Serialize};
+
+use crate::launchers::runners;
+
+// Set by the launcher itself from the runner selection and the Steam install.
+const RESERVED_ENV: [&str; 3] = ["WINEPREFIX", "PROTONPATH", "SteamGameId"];
+
+// Passed by the launcher itself when a save is loaded.
+const RESERVED_ARGS: [&str; 2] = ["game_startup_mode", "campaign_load"];
+
+/// Extra launch settings of a profile, merged into the launch command before
+/// the game is spawned.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LaunchOptions {
+    /// Appended after the mod list and the save to load.
+    pub args: Vec<String>,
+    /// e.g. `DXVK_HUD=fps` or `PROTON_LOG=1`.
+    pub env: BTreeMap<String, String>,
+    /// Commands the launch is wrapped in, outermost first, e.g. `gamemoderun`
+    /// or `mangohud --dlsym`. Split on whitespace. Only used on Linux.
+    pub wrappers: Vec<String>,
+}
+
+impl LaunchOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        for arg in &self.args {
+            if arg.trim().is_empty() {
+                return Err(String::from("empty game argument"));
+            }
+            if arg.contains('\0') {
+                return Err(format!("game argument '{}' contains a NUL byte", arg));
+            }
+            if RESERVED_ARGS.contains(&arg.as_str()) || arg.ends_with(".txt;") {
+                return Err(format!("game argument '{}' is set by the launcher", arg));
+            }
+        }
+
+        for (name, value) in &self.env {
+            if !is_env_name(name) {
+                return Err(format!(
+                    "'{}' is not a valid environment variable name",
+                    name
+                ));
+            }
+            if RESERVED_ENV.contains(&name.as_str()) {
+                return Err(format!(
+                    "{} is set by the launcher, use the runner selection instead",
+                    name
+                ));
+            }
+            if value.contains('\0') {
+                return Err(format!("value of {} contains a NUL byte", name));
+            }
+        }
+
+        for wrapper in &self.wrappers {
+            let Some(program) = wrapper.split_whitespace().next() else {
+                return Err(String::from("empty wrapper command"));
+            };
+            if wrapper.contains('\0') {
+                return Err(format!("wrapper '{}' contains a NUL byte", wrapper));
+            }
+
+            let path = Path::new(program);
+            let found = if path.components().count() > 1 {
+                path.is_file()
+            } else {
+                runners::find_in_path(program).is_some()
+            };
+            if !found {
+                return Err(format!("wrapper '{}' not found", program));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Add the extra arguments and environment variables to the command.
+    pub fn apply(&self, command: &mut Command) {
+        command.args(&self.args);
+        command.envs(&self.env);
+    }
+
+    /// The command run through the wrappers, with its environment and working
+    /// directory kept, or `None` without wrappers. `Command` can't be prefixed
+    /// in place, so it's rebuilt.
+    pub fn wrap(&self, command: &Command) -> Option<Command> {
+        let mut tokens = self.wrappers.iter().flat_map(|w| w.split_whitespace());
+
+        let mut wrapped = Command::new(tokens.next()?);
+        wrapped
+            .args(tokens)
+            .arg(command.get_program())
+            .args(command.get_args());
+
+        for (name, value) in command.get_envs() {
+            match value {
+                Some(value) => wrapped.env(name, value),
+                None => wrapped.env_remove(name),
+            };
+        }
+
+        if let Some(dir) = command.get_current_dir() {
+            wrapped.current_dir(dir);
+        }
+
+        Some(wrapped)
+    }
+}
+
+fn is_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
@@ -0,0 +1,124 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    dto::launchers::{RunnerDto, RunnerKind},
+    utils::steam::SteamConfig,
+};
+
+/// What runs the game on Linux, chosen per game with an optional override per
+/// profile. Ignored on Windows.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum RunnerSelection {
+    /// The Proton version Steam set up the game's prefix with, or umu's default
+    /// when that can't be read.
+    #[default]
+    Steam,
+    /// A Proton build folder, passed to umu as `PROTONPATH`.
+    Proton { path: PathBuf },
+    /// A Wine binary, run directly instead of through umu.
+    Wine { path: PathBuf },
+}
+
+/// Where the umu-launcher used to start the game on Linux comes from.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum UmuSource {
+    /// Our own install of the latest release, only updated through `update_umu_launcher`.
+    #[default]
+    Latest,
+    /// Our own install of a specific release tag.
+    Pinned { tag: String },
+    /// An `umu-run` installed on the system; without a path it's looked up in `PATH`.
+    System { path: Option<PathBuf> },
+}
+
+impl RunnerSelection {
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Self::Steam => Ok(()),
+            Self::Proton { path } if is_proton_dir(path) => Ok(()),
+            Self::Proton { path } => Err(format!("{} is not a Proton build", path.display())),
+            Self::Wine { path } if path.is_file() => Ok(()),
+            Self::Wine { path } => Err(format!("{} is not a Wine binary", path.display())),
+        }
+    }
+}
+
+/// Proton builds installed through Steam or in `compatibilitytools.d`, and the
+/// system Wine.
+pub fn discover(steam_config: &SteamConfig) -> Vec<RunnerDto> {
+    let mut runners: Vec<RunnerDto> = vec![];
+
+    if let Some(steam_path) = steam_config.get_steam_path() {
+        runners.extend(proton_builds(&steam_path.join("compatibilitytools.d")));
+    }
+
+    let mut libraries: Vec<PathBuf> = steam_config
+        .get_steam_dir()
+        .and_then(|steam_dir| steam_dir.libraries().ok())
+        .map(|libraries| {
+            libraries
+                .filter_map(Result::ok)
+                .map(|library| library.path().to_path_buf())
+                .collect()
+        })
+        .unwrap_or_default();
+    libraries.extend(steam_config.get_steam_library_path());
+    libraries.sort();
+    libraries.dedup();
+
+    for library in libraries {
+        runners.extend(proton_builds(&library.join("steamapps").join("common")));
+    }
+
+    if let Some(wine) = find_in_path("wine") {
+        runners.push(RunnerDto {
+            name: String::from("System Wine"),
+            path: wine,
+            kind: RunnerKind::Wine,
+        });
+    }
+
+    runners.sort_by(|a, b| a.name.cmp(&b.name));
+    runners.dedup_by(|a, b| a.path == b.path);
+
+    log::debug!("Discovered {} runner(s)", runners.len());
+
+    runners
+}
+
+fn proton_builds(dir: &Path) -> Vec<RunnerDto> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| is_proton_dir(path))
+        .map(|path| RunnerDto {
+            name: path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+            path,
+            kind: RunnerKind::Proton,
+        })
+        .collect()
+}
+
+// every Proton build ships a `proton` script at its root
+fn is_proton_dir(path: &Path) -> bool {
+    path.join("proton").is_file()
+}
+
+pub(crate) fn find_in_path(name: &str) -> Option<PathBuf> {
+    std::env::var_os("PATH").and_then(|paths| {
+        std::env::split_paths(&paths)
+            .map(|dir| dir.join(name))
+            .find(|path| path.is_file())
+    })
+}
@@ -0,0 +1,281 @@
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+    process::Command,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+
+use crate::{
+    dto::launchers::UmuStatusDto,
+    launchers::runners::{self, UmuSource},
+    stores::settings::SettingsStore,
+    utils,
+};
+
+const RELEASES_API: &str =
+    "https://api.github.com/repos/Open-Wine-Components/umu-launcher/releases";
+
+const RELEASES_API_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The umu-run executable a launch goes through.
+#[derive(Debug, Clone)]
+pub enum UmuRunner {
+    Managed(PathBuf), // the zipapp we installed, run through python
+    System(PathBuf),
+}
+
+impl UmuRunner {
+    pub fn command(&self) -> Command {
+        match self {
+            Self::Managed(path) => {
+                let mut command = Command::new("python");
+                command.arg(path);
+                command
+            }
+            Self::System(path) => Command::new(path),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Managed(path) | Self::System(path) => path,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LauncherRelease {
+    tag: Option<String>, // missing for installs made before releases were tagged here
+    release_date: String,
+    #[serde(skip)]
+    url: String,
+}
+
+pub(crate) fn source(app_handle: &tauri::AppHandle) -> UmuSource {
+    SettingsStore::get_store(app_handle)
+        .and_then(|store| SettingsStore::from_entries(store.entries()))
+        .map(|settings| settings.umu_source)
+        .unwrap_or_default()
+}
+
+/// The runner to launch with. Never touches the network when a usable install
+/// exists, so the game can be started offline; updates go through [`update`].
+pub(crate) async fn resolve_runner(
+    app_handle: &tauri::AppHandle,
+) -> Result<UmuRunner, Box<dyn Error>> {
+    let runner_path = runner_path(app_handle)?;
+    let installed = installed_release(app_handle).filter(|_| runner_path.exists());
+
+    match source(app_handle) {
+        UmuSource::System { path } => find_system_runner(path).map(UmuRunner::System),
+        UmuSource::Latest => match installed {
+            Some(release) => {
+                log::info!("Using installed umu-launcher ({})", describe(&release));
+                Ok(UmuRunner::Managed(runner_path))
+            }
+            None => {
+                log::info!("umu-launcher not installed, downloading the latest release");
+                install(app_handle, fetch_release(None).await?).await
+            }
+        },
+        UmuSource::Pinned { tag } => match installed {
+            Some(release) if release.tag.as_deref() == Some(tag.as_str()) => {
+                Ok(UmuRunner::Managed(runner_path))
+            }
+            Some(release) => match fetch_release(Some(&tag)).await {
+                Ok(pinned) => install(app_handle, pinned).await,
+                Err(e) => {
+                    log::warn!(
+                        "Couldn't get pinned umu-launcher {} ({}), using the installed {}",
+                        tag,
+                        e,
+                        describe(&release)
+                    );
+                    Ok(UmuRunner::Managed(runner_path))
+                }
+            },
+            None => install(app_handle, fetch_release(Some(&tag)).await?).await,
+        },
+    }
+}
+
+/// Install the latest or pinned release when it isn't the installed one.
+pub(crate) async fn update(app_handle: &tauri::AppHandle) -> Result<UmuRunner, Box<dyn Error>> {
+    let runner_path = runner_path(app_handle)?;
+    let installed = installed_release(app_handle).filter(|_| runner_path.exists());
+
+    let (release, pinned) = match source(app_handle) {
+        UmuSource::System { .. } => {
+            return Err("umu-run is provided by the system and can't be updated from here".into());
+        }
+        UmuSource::Latest => (fetch_release(None).await?, false),
+        UmuSource::Pinned { tag } => (fetch_release(Some(&tag)).await?, true),
+    };
+
+    if let Some(installed) = installed
+        && is_up_to_date(&installed, &release, pinned)?
+    {
+        log::info!(
+            "umu-launcher up to date (current={}, remote={})",
+            describe(&installed),
+            describe(&release)
+        );
+        return Ok(UmuRunner::Managed(runner_path));
+    }
+
+    install(app_handle, release).await
+}
+
+pub(crate) fn status(app_handle: &tauri::AppHandle) -> UmuStatusDto {
+    let source = source(app_handle);
+    let installed = installed_release(app_handle);
+
+    let runner_path = match &source {
+        UmuSource::System { path } => find_system_runner(path.clone()).ok(),
+        _ => runner_path(app_handle).ok().filter(|path| path.exists()),
+    };
+
+    UmuStatusDto {
+        source,
+        installed_tag: installed.as_ref().and_then(|r| r.tag.clone()),
+        installed_release_date: installed.map(|r| r.release_date),
+        runner_path,
+    }
+}
+
+fn base_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, Box<dyn Error>> {
+    let app_data = app_handle.path().app_data_dir()?;
+    Ok(app_data.join("umu-launcher/"))
+}
+
+fn runner_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, Box<dyn Error>> {
+    Ok(base_path(app_handle)?.join("umu/umu-run"))
+}
+
+// The version file used to hold only the release date, newer installs store
+// the release as JSON.
+fn installed_release(app_handle: &tauri::AppHandle) -> Option<LauncherRelease> {
+    let contents = std::fs::read_to_string(base_path(app_handle).ok()?.join("version")).ok()?;
+
+    serde_json::from_str(&contents).ok().or_else(|| {
+        Some(LauncherRelease {
+            tag: None,
+            release_date: contents.trim().to_string(),
+            url: String::new(),
+        })
+    })
+}
+
+// A pinned release can be older than the installed one, so only the tag counts.
+fn is_up_to_date(
+    installed: &LauncherRelease,
+    remote: &LauncherRelease,
+    pinned: bool,
+) -> Result<bool, Box<dyn Error>> {
+    if installed.tag.is_some() && installed.tag == remote.tag {
+        return Ok(true);
+    }
+
+    if pinned {
+        return Ok(false);
+    }
+
+    let installed_date = chrono::DateTime::parse_from_rfc3339(&installed.release_date)?;
+    let remote_date = chrono::DateTime::parse_from_rfc3339(&remote.release_date)?;
+
+    Ok(remote_date <= installed_date)
+}
+
+fn describe(release: &LauncherRelease) -> String {
+    match &release.tag {
+        Some(tag) => format!("{} ({})", tag, release.release_date),
+        None => release.release_date.clone(),
+    }
+}
+
+async fn install(
+    app_handle: &tauri::AppHandle,
+    release: LauncherRelease,
+) -> Result<UmuRunner, Box<dyn Error>> {
+    let launcher_dir = base_path(app_handle)?;
+
+    log::info!("Downloading umu-launcher {}", describe(&release));
+
+    let mut res = utils::download(app_handle, &release.url, "linux-runner").await?;
+
+    let mut tar = tar::Archive::new(res.body_mut().as_reader());
+
+    log::info!("Extracting linux runner to {}", launcher_dir.display());
+
+    tar.unpack(&launcher_dir)?;
+
+    // write the release to a version file
+    std::fs::write(launcher_dir.join("version"), serde_json::to_vec(&release)?)?;
+
+    Ok(UmuRunner::Managed(runner_path(app_handle)?))
+}
+
+async fn fetch_release(tag: Option<&str>) -> Result<LauncherRelease, Box<dyn Error>> {
+    let url = match tag {
+        Some(tag) => format!("{}/tags/{}", RELEASES_API, tag),
+        None => format!("{}/latest", RELEASES_API),
+    };
+
+    let res = ureq::get(&url)
+        .config()
+        .timeout_global(Some(RELEASES_API_TIMEOUT))
+        .build()
+        .call()?
+        .body_mut()
+        .read_json::<serde_json::Value>()?;
+
+    let Some(release_date) = res.get("updated_at").and_then(|v| v.as_str()) else {
+        return Err("Failed to get updated_at from GitHub API response".into());
+    };
+
+    let Some(assets) = res.get("assets").and_then(|v| v.as_array()) else {
+        return Err("Failed to get assets from GitHub API response".into());
+    };
+
+    let zip: Option<&serde_json::Value> = assets.iter().find(|asset| {
+        let Some(o) = asset.as_object() else {
+            return false;
+        };
+
+        let Some(name) = o.get("name").and_then(|v| v.as_str()) else {
+            return false;
+        };
+
+        name.contains("zipapp")
+    });
+
+    let zipapp_url = zip
+        .and_then(|z| z.get("browser_download_url"))
+        .and_then(|v| v.as_str())
+        .ok_or("Failed to get zipapp URL from GitHub API response")?;
+
+    Ok(LauncherRelease {
+        tag: res
+            .get("tag_name")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        release_date: release_date.to_string(),
+        url: zipapp_url.to_string(),
+    })
+}
+
+fn find_system_runner(path: Option<PathBuf>) -> Result<PathBuf, Box<dyn Error>> {
+    if let Some(path) = path {
+        return if path.is_file() {
+            Ok(path)
+        } else {
+            Err(format!("umu-run not found at {}", path.display()).into())
+        };
+    }
+
+    runners::find_in_path("umu-run").ok_or_else(|| "umu-run not found in PATH".into())
+}
@@ -1,31 +1,33 @@
 use std::error::Error;
-use std::os::windows::process::CommandExt;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Child, Command};
 
+use crate::launchers::{options::LaunchOptions, runners::RunnerSelection};
 use crate::utils::steam::SteamConfig;
 use crate::{defaults::games::DefaultGameInfo, supported_games::SupportedGames};
 
 #[derive(Debug)]
 pub(crate) struct WindowsLauncher {
-    command: Command,
+    command: Option<Command>, // built for the game being launched
     steam_config: SteamConfig,
     running_exe: Option<&'static str>, // use this to kill
 }
 
 impl WindowsLauncher {
-    pub async fn new(app_handler: &tauri::AppHandle) -> Self {
-        let command = Command::new("cmd");
+    pub async fn new(
+        app_handler: &tauri::AppHandle,
+        _runner: &RunnerSelection, // the game runs natively
+    ) -> Result<Self, Box<dyn Error>> {
         let steam_config = SteamConfig::from_app_handle(app_handler).unwrap_or(SteamConfig {
             steam_path: None,
             steam_library_path: None,
         });
 
-        Self {
-            command,
+        Ok(Self {
+            command: None,
             steam_config,
             running_exe: None,
-        }
+        })
     }
 }
 
@@ -35,7 +37,10 @@ impl super::GameManager for WindowsLauncher {
         game_id: SupportedGames,
         game_path: &Path,
         save_path: Option<&PathBuf>,
-    ) -> Result<(), Box<dyn Error>> {
+        mod_list: &str,
+        _runner: &RunnerSelection, // the game runs natively
+        launch_options: &LaunchOptions,
+    ) -> Result<Child, Box<dyn Error>> {
         let game_id_str: String = game_id.into();
         log::info!(
             "WindowsLauncher::launch_game game={}, path={}, save={:?}",
@@ -58,10 +63,13 @@ impl super::GameManager for WindowsLauncher {
         let game_preset =
             DefaultGameInfo::find_by_id(game_id).ok_or("Couldn't find game preset")?;
 
-        let command = self.get_command();
+        // started directly rather than through `cmd /C start`, so the child is the
+        // game itself and the monitor gets its exit code
+        let command = self
+            .command
+            .insert(Command::new(game_path.join(game_preset.executable_name)));
         command.current_dir(game_path);
-        command.raw_arg(game_preset.executable_name);
-        command.arg("used_mods.txt;");
+        command.arg(format!("{};", mod_list));
 
         if let Some(save_path) = save_path {
             log::info!("Loading save: {}", save_path.display());
@@ -71,13 +79,22 @@ impl super::GameManager for WindowsLauncher {
                 .arg(save_path);
         }
 
+        launch_options.apply(command);
+
+        if !launch_options.wrappers.is_empty() {
+            log::warn!(
+                "Ignoring wrapper commands on Windows: {:?}",
+                launch_options.wrappers
+            );
+        }
+
         log::info!("Spawning game process");
         log::debug!("Running command: {:?}", command);
 
-        let _ = command.spawn(); // do not wait
+        let child = command.spawn()?; // do not wait, the monitor follows the game
 
         self.running_exe = Some(game_preset.executable_name);
-        Ok(())
+        Ok(child)
     }
 
     fn kill_game(&mut self) -> Result<(), Box<dyn Error>> {
@@ -102,6 +119,8 @@ impl super::GameManager for WindowsLauncher {
     }
 
     fn get_command(&mut self) -> &mut Command {
-        self.command.args(["/C", "start"])
+        self.command
+            .as_mut()
+            .expect("the command is built when launching the game")
     }
 }
@@ -3,10 +3,12 @@ use tauri_plugin_log::{Target, TargetKind};
 
 pub mod commands;
 pub mod defaults;
+pub mod diagnostics;
 pub mod dto;
 pub mod events;
 pub mod launchers;
 pub mod mods;
+pub mod saves;
 pub mod state;
 pub mod stores;
 pub mod supported_games;
@@ -52,6 +54,13 @@ pub fn run() {
             log::info!("Config dir: {:?}", app.path().config_dir());
             log::info!("Log dir: {:?}", app.path().app_log_dir());
 
+            mods::cache::PackCache::init(utils::path::generate_store_path(
+                app_handle,
+                "pack_cache.json",
+            ));
+
+            mods::scan::init(app_handle.clone());
+
             let default_state = state::State::new(app_handle.clone());
 
             stores::settings::SettingsStore::get_store(app_handle)
@@ -65,11 +74,19 @@ pub fn run() {
             commands::games::start_game,
             commands::games::stop_game,
             commands::games::get_saves,
+            commands::games::delete_save,
+            commands::games::rename_save,
+            commands::games::check_save_profile,
+            commands::games::get_save_header,
+            commands::games::get_save_backups,
+            commands::games::restore_save_backup,
             commands::games::update_game,
             commands::games::check_path_exists,
             commands::games::get_supported_games,
             commands::games::get_game,
             commands::games::set_default_profile,
+            commands::games::get_play_history,
+            commands::games::collect_diagnostics,
             commands::profiles::create_profile,
             commands::profiles::update_profile,
             commands::profiles::rename_profile,
@@ -79,11 +96,38 @@ pub fn run() {
             commands::profiles::set_profile_mods,
             commands::profiles::add_profile_mods,
             commands::profiles::remove_profile_mods,
+            commands::profiles::import_launcher_profile,
+            commands::profiles::import_mod_script,
+            commands::profiles::create_profile_from_save,
+            commands::profiles::export_profile,
+            commands::profiles::import_shared_profile,
+            commands::profiles::diff_profiles,
+            commands::profiles::merge_profiles,
+            commands::profiles::get_profile_snapshots,
+            commands::profiles::restore_profile_snapshot,
+            commands::profiles::diff_profile_snapshot,
+            commands::mods::get_profile_conflicts,
+            commands::mods::validate_profile,
+            commands::mods::set_load_order_rules,
+            commands::mods::import_masterlist,
+            commands::mods::remove_masterlist,
             commands::settings::get_user_settings,
             commands::settings::set_default_game,
             commands::settings::set_invert_mod_names,
+            commands::settings::set_dependency_policy,
+            commands::settings::set_save_backups,
+            commands::settings::set_umu_source,
             commands::settings::update_settings,
             commands::settings::get_log_directory,
+            #[cfg(target_os = "linux")]
+            commands::launchers::get_umu_status,
+            #[cfg(target_os = "linux")]
+            commands::launchers::update_umu_launcher,
+            commands::launchers::get_runners,
+            commands::launchers::set_game_runner,
+            commands::launchers::set_profile_runner,
+            commands::launchers::set_profile_launch_options,
+            commands::launchers::set_game_hooks,
             commands::groups::create_group,
             commands::groups::update_group,
             commands::groups::rename_group,
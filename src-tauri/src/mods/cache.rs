@@ -0,0 +1,162 @@
+use std::{
+    collections::HashMap,
+    fs::Metadata,
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+    time::UNIX_EPOCH,
+};
+
+use serde::{Deserialize, Serialize};
+
+// Parsing a pack with rpfm_lib is by far the slowest part of listing mods, and
+// the mod list is rebuilt on every refresh. The metadata we extract only changes
+// when the file does, so it's kept on disk and reused across runs.
+static PACK_CACHE: OnceLock<Mutex<PackCache>> = OnceLock::new();
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CacheEntry {
+    size: u64,
+    modified: u64, // milliseconds since the unix epoch
+    dependencies: Vec<(bool, String)>,
+}
+
+#[derive(Debug)]
+pub struct PackCache {
+    path: PathBuf,
+    entries: HashMap<PathBuf, CacheEntry>,
+    dirty: bool,
+    hits: usize,
+    misses: usize,
+}
+
+impl PackCache {
+    /// Load the cache file. Must be called once during setup; until then every
+    /// lookup is a miss and nothing is persisted.
+    pub fn init(path: PathBuf) {
+        let entries = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+                log::warn!(
+                    "Discarding unreadable pack cache {}: {:?}",
+                    path.display(),
+                    e
+                );
+                HashMap::new()
+            }),
+            Err(_) => HashMap::new(),
+        };
+
+        log::info!(
+            "Pack metadata cache loaded from {} ({} entries)",
+            path.display(),
+            entries.len()
+        );
+
+        let _ = PACK_CACHE.set(Mutex::new(Self {
+            path,
+            entries,
+            dirty: false,
+            hits: 0,
+            misses: 0,
+        }));
+    }
+
+    /// Cached dependencies for the pack at `path`, or the result of `parse` when
+    /// the pack is new or its size or modification time changed. A pack `parse`
+    /// can't read (still being downloaded, for example) has no dependencies and
+    /// isn't cached, so it's parsed again next time.
+    pub fn dependencies<F>(path: &Path, metadata: &Metadata, parse: F) -> Vec<(bool, String)>
+    where
+        F: FnOnce() -> Option<Vec<(bool, String)>>,
+    {
+        let Some(cell) = PACK_CACHE.get() else {
+            return parse().unwrap_or_default();
+        };
+
+        let size = metadata.len();
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .and_then(|d| u64::try_from(d.as_millis()).ok())
+            .unwrap_or_default();
+
+        if let Ok(mut cache) = cell.lock() {
+            let cached = cache
+                .entries
+                .get(path)
+                .filter(|e| e.size == size && e.modified == modified)
+                .map(|e| e.dependencies.clone());
+
+            if let Some(dependencies) = cached {
+                cache.hits += 1;
+                return dependencies;
+            }
+        }
+
+        // parse without holding the lock
+        let parsed = parse();
+
+        if let Ok(mut cache) = cell.lock() {
+            cache.misses += 1;
+
+            if let Some(dependencies) = &parsed {
+                cache.dirty = true;
+                cache.entries.insert(
+                    path.to_path_buf(),
+                    CacheEntry {
+                        size,
+                        modified,
+                        dependencies: dependencies.clone(),
+                    },
+                );
+            }
+        }
+
+        parsed.unwrap_or_default()
+    }
+
+    /// Log the hit/miss stats since the last flush and write the cache to disk if
+    /// anything changed. Entries for packs that no longer exist are dropped.
+    pub fn flush() {
+        let Some(cell) = PACK_CACHE.get() else {
+            return;
+        };
+
+        let Ok(mut cache) = cell.lock() else {
+            return;
+        };
+
+        log::info!(
+            "Pack metadata cache: {} hit(s), {} miss(es)",
+            cache.hits,
+            cache.misses
+        );
+        cache.hits = 0;
+        cache.misses = 0;
+
+        let before = cache.entries.len();
+        cache.entries.retain(|path, _| path.exists());
+        if cache.entries.len() != before {
+            cache.dirty = true;
+        }
+
+        if !cache.dirty {
+            return;
+        }
+
+        let result = serde_json::to_vec(&cache.entries)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| {
+                if let Some(parent) = cache.path.parent() {
+                    std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+                }
+                std::fs::write(&cache.path, bytes).map_err(|e| e.to_string())
+            });
+
+        match result {
+            Ok(()) => cache.dirty = false,
+            Err(e) => log::warn!("Failed to write pack cache {}: {}", cache.path.display(), e),
+        }
+    }
+}
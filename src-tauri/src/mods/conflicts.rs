@@ -0,0 +1,222 @@
+use std::collections::BTreeMap;
+
+use rpfm_lib::{
+    files::{Container, DecodeableExtraData, FileType, RFileDecoded, pack::Pack},
+    schema::Schema,
+};
+
+use crate::{
+    dto::{
+        conflicts::{ConflictReportDto, FileConflictDto, TableConflictDto},
+        mods::ModResponseDto,
+    },
+    mods::pack::ModPack,
+    supported_games::SupportedGames,
+};
+
+/// Indexes the internal files of every enabled pack in a profile to find the
+/// ones that overwrite each other.
+///
+/// The game gives priority to the packs listed first in the mod list script, so the
+/// pack with the lowest `order` wins every overlap it takes part in.
+///
+/// DB table files with different names are merged by the game row by row, a row
+/// from a higher priority pack replacing the one with the same key. With the table
+/// schema, the rows are decoded and their keys indexed too.
+pub(crate) struct ConflictAnalyzer {
+    packs: Vec<String>,      // enabled packs, highest priority first
+    unreadable: Vec<String>, // packs that were enabled but couldn't be opened
+    files: BTreeMap<String, (String, Vec<usize>)>, // lowercase path -> (original path, pack indexes)
+    rows: BTreeMap<(String, String), Vec<usize>>,  // (db table, row key) -> pack indexes
+    tables_analyzed: bool,
+}
+
+impl ConflictAnalyzer {
+    pub fn new(
+        game_id: SupportedGames,
+        profile_mods: &[ModResponseDto],
+        schema: Option<&Schema>,
+    ) -> Self {
+        let mut enabled: Vec<&ModResponseDto> = profile_mods
+            .iter()
+            .filter(|m| m.enabled && m.path.is_some())
+            .collect();
+        enabled.sort_by_key(|m| m.order);
+
+        let mut analyzer = Self {
+            packs: vec![],
+            unreadable: vec![],
+            files: BTreeMap::new(),
+            rows: BTreeMap::new(),
+            tables_analyzed: schema.is_some(),
+        };
+
+        for m in enabled {
+            let path = m.path.as_ref().unwrap(); // filtered above
+
+            let Some(mut pack) = ModPack::read_pack(game_id, path) else {
+                analyzer.unreadable.push(m.name.clone());
+                continue;
+            };
+
+            let index = analyzer.packs.len();
+            analyzer.packs.push(m.name.clone());
+
+            // a table file already found in a higher priority pack isn't loaded
+            // at all, so its rows take no part in the merge
+            let mut tables = vec![];
+            for file_path in pack.files().keys() {
+                if analyzer.index_file(index, file_path) {
+                    tables.push(file_path.clone());
+                }
+            }
+
+            if let Some(schema) = schema {
+                analyzer.index_rows(index, &mut pack, &tables, schema);
+            }
+        }
+
+        log::info!(
+            "Indexed {} internal file(s) and {} table row(s) across {} pack(s) ({} unreadable)",
+            analyzer.files.len(),
+            analyzer.rows.len(),
+            analyzer.packs.len(),
+            analyzer.unreadable.len()
+        );
+
+        analyzer
+    }
+
+    // Returns whether the file is a table the pack contributes rows from.
+    fn index_file(&mut self, pack_index: usize, file_path: &str) -> bool {
+        // the game resolves paths case-insensitively
+        let key = file_path.to_lowercase();
+        let is_table = Self::db_table_name(&key).is_some();
+
+        let entry = self
+            .files
+            .entry(key)
+            .or_insert_with(|| (file_path.to_owned(), vec![]));
+
+        if !entry.1.contains(&pack_index) {
+            entry.1.push(pack_index);
+        }
+
+        is_table && entry.1[0] == pack_index
+    }
+
+    // db/<table_name>/<file_name>
+    fn db_table_name(path: &str) -> Option<&str> {
+        let mut segments = path.split('/');
+        match (segments.next(), segments.next(), segments.next()) {
+            (Some("db"), Some(table), Some(_)) => Some(table),
+            _ => None,
+        }
+    }
+
+    fn index_rows(
+        &mut self,
+        pack_index: usize,
+        pack: &mut Pack,
+        tables: &[String],
+        schema: &Schema,
+    ) {
+        let mut extra_data = DecodeableExtraData::default();
+        extra_data.set_schema(Some(schema));
+        let extra_data = Some(extra_data);
+
+        for file_path in tables {
+            let Some(file) = pack.files_mut().get_mut(file_path) else {
+                continue;
+            };
+
+            if file.file_type() != FileType::DB {
+                continue;
+            }
+
+            // tables without a definition for their version can't be decoded
+            let db = match file.decode(&extra_data, false, true) {
+                Ok(Some(RFileDecoded::DB(db))) => db,
+                Ok(_) => continue,
+                Err(e) => {
+                    log::debug!(
+                        "Skipping table {} in {}: {:?}",
+                        file_path,
+                        self.packs[pack_index],
+                        e
+                    );
+                    continue;
+                }
+            };
+
+            let table = db.table_name().to_lowercase();
+            let key_columns = db.definition().key_column_positions();
+            if key_columns.is_empty() {
+                continue;
+            }
+
+            for row in db.data().iter() {
+                let key: Vec<String> = key_columns
+                    .iter()
+                    .filter_map(|i| row.get(*i))
+                    .map(|value| value.data_to_string().into_owned())
+                    .collect();
+
+                let packs = self.rows.entry((table.clone(), key.join("|"))).or_default();
+                if !packs.contains(&pack_index) {
+                    packs.push(pack_index);
+                }
+            }
+        }
+    }
+
+    pub fn report(self, profile_id: uuid::Uuid) -> ConflictReportDto {
+        let files: Vec<FileConflictDto> = self
+            .files
+            .values()
+            .filter(|(_, packs)| packs.len() > 1)
+            .map(|(path, packs)| FileConflictDto {
+                path: path.clone(),
+                winner: self.packs[packs[0]].clone(),
+                overridden: packs[1..].iter().map(|i| self.packs[*i].clone()).collect(),
+            })
+            .collect();
+
+        // rows overridden the same way are reported together
+        let mut overlaps: BTreeMap<(&str, &[usize]), Vec<String>> = BTreeMap::new();
+        for ((table, key), packs) in &self.rows {
+            if packs.len() > 1 {
+                overlaps
+                    .entry((table.as_str(), packs.as_slice()))
+                    .or_default()
+                    .push(key.clone());
+            }
+        }
+
+        let tables: Vec<TableConflictDto> = overlaps
+            .into_iter()
+            .map(|((table, packs), keys)| TableConflictDto {
+                table: table.to_owned(),
+                keys,
+                winner: self.packs[packs[0]].clone(),
+                overridden: packs[1..].iter().map(|i| self.packs[*i].clone()).collect(),
+            })
+            .collect();
+
+        log::info!(
+            "Conflict report for profile {}: {} file conflict(s), {} table row conflict(s)",
+            profile_id,
+            files.len(),
+            tables.iter().map(|t| t.keys.len()).sum::<usize>()
+        );
+
+        ConflictReportDto {
+            profile_id,
+            packs: self.packs,
+            unreadable: self.unreadable,
+            files,
+            tables,
+            tables_analyzed: self.tables_analyzed,
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    dto::diff::{EnabledChangeDto, OrderChangeDto, ProfileDiffDto},
+    stores::games::ProfileModInfo,
+};
+
+/// Compare two profile mod lists.
+///
+/// Order changes only look at the mods both lists share. The largest set of
+/// mods that kept their relative order is left out, so moving a single mod
+/// reports that mod alone rather than everything it shifted.
+pub(crate) fn diff_mods(a: &[ProfileModInfo], b: &[ProfileModInfo]) -> ProfileDiffDto {
+    let a_by_name: HashMap<&str, &ProfileModInfo> =
+        a.iter().map(|m| (m.name.as_str(), m)).collect();
+    let b_by_name: HashMap<&str, &ProfileModInfo> =
+        b.iter().map(|m| (m.name.as_str(), m)).collect();
+
+    let only_in_a = a
+        .iter()
+        .filter(|m| !b_by_name.contains_key(m.name.as_str()))
+        .map(|m| m.name.clone())
+        .collect();
+    let only_in_b = b
+        .iter()
+        .filter(|m| !a_by_name.contains_key(m.name.as_str()))
+        .map(|m| m.name.clone())
+        .collect();
+
+    let enabled_changes = a
+        .iter()
+        .filter_map(|m| {
+            let other = b_by_name.get(m.name.as_str())?;
+            (m.enabled != other.enabled).then(|| EnabledChangeDto {
+                name: m.name.clone(),
+                enabled_a: m.enabled,
+                enabled_b: other.enabled,
+            })
+        })
+        .collect();
+
+    let shared_a = shared_mods(a, &b_by_name);
+    let shared_b = shared_mods(b, &a_by_name);
+    let kept = longest_common_subsequence(&shared_a, &shared_b);
+
+    let position_b: HashMap<&str, usize> = shared_b
+        .iter()
+        .enumerate()
+        .map(|(i, name)| (*name, i + 1))
+        .collect();
+
+    let order_changes = shared_a
+        .iter()
+        .enumerate()
+        .filter(|(_, name)| !kept.contains(*name))
+        .map(|(i, name)| OrderChangeDto {
+            name: name.to_string(),
+            position_a: i + 1,
+            position_b: position_b[name],
+        })
+        .collect();
+
+    ProfileDiffDto {
+        only_in_a,
+        only_in_b,
+        enabled_changes,
+        order_changes,
+    }
+}
+
+// names of the mods also found in `other`, in load order
+fn shared_mods<'a>(
+    mods: &'a [ProfileModInfo],
+    other: &HashMap<&str, &ProfileModInfo>,
+) -> Vec<&'a str> {
+    let mut shared: Vec<&ProfileModInfo> = mods
+        .iter()
+        .filter(|m| other.contains_key(m.name.as_str()))
+        .collect();
+    shared.sort_by_key(|m| m.order);
+    shared.into_iter().map(|m| m.name.as_str()).collect()
+}
+
+fn longest_common_subsequence<'a>(a: &[&'a str], b: &[&'a str]) -> HashSet<&'a str> {
+    // lengths[i][j] = LCS length of a[i..] and b[j..]
+    let mut lengths = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lengths[i][j] = if a[i] == b[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut kept = HashSet::new();
+    let (mut i, mut j) = (0, 0);
+
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            kept.insert(a[i]);
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    kept
+}
@@ -0,0 +1,163 @@
+use std::{collections::HashMap, path::Path};
+
+use crate::{
+    mods::pack::ModPack,
+    stores::games::{Profile, ProfileModInfo},
+};
+
+/// A mod read from an external load order, in the order it should be loaded.
+#[derive(Debug, Clone)]
+pub struct ImportedMod {
+    pub name: String,
+    pub enabled: bool,
+}
+
+// One entry of the official launcher's mod list. The file holds every game the
+// launcher manages, so entries are filtered by `game`.
+#[derive(Debug, Clone, serde::Deserialize)]
+struct LauncherModEntry {
+    game: String,
+    packfile: String,
+    #[serde(default)]
+    active: bool,
+    #[serde(default)]
+    order: u32,
+}
+
+/// Read the mod list kept by the official CA launcher, sorted by its load order.
+pub(crate) fn read_launcher_moddata(
+    path: &Path,
+    game_key: &str,
+) -> Result<Vec<ImportedMod>, Box<dyn std::error::Error>> {
+    let bytes = std::fs::read(path)?;
+    let mut entries: Vec<LauncherModEntry> = serde_json::from_slice(&bytes)?;
+
+    entries.retain(|e| e.game == game_key);
+    entries.sort_by_key(|e| e.order);
+
+    log::info!(
+        "Read {} launcher mod entries for '{}' from {}",
+        entries.len(),
+        game_key,
+        path.display()
+    );
+
+    Ok(entries
+        .into_iter()
+        .map(|e| {
+            // the launcher may store the full path of the pack
+            let file_name = e.packfile.rsplit(['/', '\\']).next().unwrap_or(&e.packfile);
+
+            ImportedMod {
+                name: ModPack::dependency_name(file_name).to_owned(),
+                enabled: e.active,
+            }
+        })
+        .collect())
+}
+
+/// Mods and working directories read from a `used_mods.txt`-style script.
+#[derive(Debug, Clone, Default)]
+pub struct ModScript {
+    pub mods: Vec<ImportedMod>,
+    pub workshop_ids: Vec<u64>, // published file ids of the workshop directories added
+}
+
+/// Parse the `add_working_directory "<dir>";` and `mod "<name>.pack";` lines of a
+/// mod script. Anything else is ignored.
+pub(crate) fn parse_mod_script(contents: &str) -> ModScript {
+    let mut script = ModScript::default();
+
+    for line in contents.lines().map(str::trim) {
+        if let Some(rest) = line.strip_prefix("mod ") {
+            let Some(file_name) = quoted_value(rest) else {
+                log::warn!("Skipping malformed mod line: {}", line);
+                continue;
+            };
+
+            script.mods.push(ImportedMod {
+                name: ModPack::dependency_name(file_name).to_owned(),
+                enabled: true,
+            });
+        } else if let Some(rest) = line.strip_prefix("add_working_directory ") {
+            // workshop directories end with the published file id, whatever the
+            // machine the script was written on
+            let workshop_id = quoted_value(rest).and_then(|dir| {
+                dir.rsplit(['/', '\\'])
+                    .find(|segment| !segment.is_empty())
+                    .and_then(|segment| segment.parse::<u64>().ok())
+            });
+
+            match workshop_id {
+                Some(id) if !script.workshop_ids.contains(&id) => script.workshop_ids.push(id),
+                Some(_) => {}
+                None => log::debug!("Ignoring non-workshop working directory: {}", line),
+            }
+        }
+    }
+
+    script
+}
+
+fn quoted_value(value: &str) -> Option<&str> {
+    let start = value.find('"')? + 1;
+    let end = start + value[start..].find('"')?;
+    Some(&value[start..end])
+}
+
+/// Match imported mods against the packs found on disk, keeping the imported
+/// order. Returns the profile mods and the names that have no pack on disk.
+/// Workshop packs are found by name, as they sit in the same directories the
+/// scripts point at.
+pub(crate) fn resolve_mods(
+    imported: Vec<ImportedMod>,
+    disk_mods: &[ModPack],
+) -> (Vec<ProfileModInfo>, Vec<String>) {
+    let on_disk: HashMap<&str, &ModPack> = disk_mods.iter().map(|m| (m.name.as_str(), m)).collect();
+
+    let mut mods: Vec<ProfileModInfo> = vec![];
+    let mut missing: Vec<String> = vec![];
+
+    for m in imported {
+        if mods.iter().any(|existing| existing.name == m.name) || missing.contains(&m.name) {
+            continue;
+        }
+
+        if !on_disk.contains_key(m.name.as_str()) {
+            log::warn!("Imported mod '{}' not found on disk", m.name);
+            missing.push(m.name);
+            continue;
+        }
+
+        mods.push(ProfileModInfo {
+            order: u32::try_from(mods.len() + 1).unwrap_or(0),
+            name: m.name,
+            enabled: m.enabled,
+            groups: None,
+        });
+    }
+
+    (mods, missing)
+}
+
+/// Replace the load order of an existing profile with the imported one. Mods that
+/// were already in the profile keep their group links, the ones missing from
+/// the import are kept disabled after the imported ones.
+pub(crate) fn apply_to_profile(profile: &mut Profile, imported: Vec<ProfileModInfo>) {
+    let mut previous = std::mem::take(&mut profile.mods);
+
+    for mut m in imported {
+        if let Some(pos) = previous.iter().position(|p| p.name == m.name) {
+            m.groups = previous.remove(pos).groups;
+        }
+        profile.mods.push(m);
+    }
+
+    for mut m in previous {
+        m.enabled = false;
+        m.order = u32::try_from(profile.mods.len() + 1).unwrap_or(0);
+        profile.mods.push(m);
+    }
+
+    profile.manual_mode = true;
+}
@@ -0,0 +1,185 @@
+use std::{collections::HashMap, error::Error, path::PathBuf, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::mods::{
+    pack::ModPack,
+    rules::{LoadOrderRule, PackPattern, RuleKind},
+};
+
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Where a masterlist was imported from, kept so it can be imported again.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum MasterlistSource {
+    File { path: PathBuf },
+    Url { url: String },
+}
+
+/// A mod in a masterlist: a number is a Workshop item (every pack it ships),
+/// a string is a pack name or pattern.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MasterlistRef {
+    WorkshopId(u64),
+    Pack(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MasterlistEntry {
+    #[serde(rename = "mod")]
+    pub target: MasterlistRef,
+    #[serde(default)]
+    pub load_after: Vec<MasterlistRef>,
+    #[serde(default)]
+    pub incompatible_with: Vec<MasterlistRef>,
+    #[serde(default)]
+    pub requires: Vec<MasterlistRef>, // also loaded before the mod
+}
+
+/// The masterlist file format, shared between users as JSON.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MasterlistFile {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub entries: Vec<MasterlistEntry>,
+}
+
+/// A masterlist as stored for a game. It's parsed at import, so applying it
+/// never needs the source again and works offline.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Masterlist {
+    pub source: MasterlistSource,
+    pub name: Option<String>,
+    pub imported_at: String,
+    pub entries: Vec<MasterlistEntry>,
+}
+
+/// A mod the masterlist says can't work without another one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MasterlistRequirement {
+    pub pack: PackPattern,
+    pub requires: Vec<String>, // any of these packs; a Workshop item that isn't installed is kept as its id
+}
+
+/// Fetch the masterlist's contents. Only a URL source touches the network.
+pub fn read(source: &MasterlistSource) -> Result<String, Box<dyn Error>> {
+    match source {
+        MasterlistSource::File { path } => Ok(std::fs::read_to_string(path)?),
+        MasterlistSource::Url { url } => Ok(ureq::get(url)
+            .config()
+            .timeout_global(Some(DOWNLOAD_TIMEOUT))
+            .build()
+            .call()?
+            .body_mut()
+            .read_to_string()?),
+    }
+}
+
+impl Masterlist {
+    pub fn parse(source: MasterlistSource, contents: &str) -> Result<Self, serde_json::Error> {
+        let file: MasterlistFile = serde_json::from_str(contents)?;
+
+        Ok(Self {
+            source,
+            name: file.name,
+            imported_at: chrono::Utc::now().to_rfc3339(),
+            entries: file.entries,
+        })
+    }
+
+    /// The entries as load order rules against the installed packs. Workshop
+    /// ids are matched through the packs found in the workshop folder; entries
+    /// about items that aren't installed are left out.
+    pub fn rules(&self, workshop_ids: &[(String, u64)]) -> Vec<LoadOrderRule> {
+        let packs = packs_by_workshop_id(workshop_ids);
+        let mut rules = vec![];
+
+        for entry in &self.entries {
+            for pack in resolve(&entry.target, &packs) {
+                for after in entry.load_after.iter().chain(&entry.requires) {
+                    rules.extend(resolve(after, &packs).into_iter().map(|after| {
+                        rule(RuleKind::LoadAfter {
+                            pack: pack.clone(),
+                            after,
+                        })
+                    }));
+                }
+
+                for other in &entry.incompatible_with {
+                    rules.extend(resolve(other, &packs).into_iter().map(|with| {
+                        rule(RuleKind::Incompatible {
+                            pack: pack.clone(),
+                            with,
+                        })
+                    }));
+                }
+            }
+        }
+
+        rules
+    }
+
+    pub fn requirements(&self, workshop_ids: &[(String, u64)]) -> Vec<MasterlistRequirement> {
+        let packs = packs_by_workshop_id(workshop_ids);
+        let mut requirements = vec![];
+
+        for entry in &self.entries {
+            let required: Vec<Vec<String>> = entry
+                .requires
+                .iter()
+                .map(|r| match r {
+                    MasterlistRef::WorkshopId(id) => packs
+                        .get(id)
+                        .cloned()
+                        .unwrap_or_else(|| vec![id.to_string()]),
+                    MasterlistRef::Pack(name) => {
+                        vec![ModPack::dependency_name(name).to_owned()]
+                    }
+                })
+                .collect();
+
+            for pack in resolve(&entry.target, &packs) {
+                requirements.extend(required.iter().map(|requires| MasterlistRequirement {
+                    pack: pack.clone(),
+                    requires: requires.clone(),
+                }));
+            }
+        }
+
+        requirements
+    }
+}
+
+fn packs_by_workshop_id(workshop_ids: &[(String, u64)]) -> HashMap<u64, Vec<String>> {
+    let mut packs: HashMap<u64, Vec<String>> = HashMap::new();
+
+    for (name, id) in workshop_ids {
+        packs.entry(*id).or_default().push(name.clone());
+    }
+
+    packs
+}
+
+fn resolve(reference: &MasterlistRef, packs: &HashMap<u64, Vec<String>>) -> Vec<PackPattern> {
+    match reference {
+        MasterlistRef::WorkshopId(id) => packs
+            .get(id)
+            .map(|names| names.iter().cloned().map(PackPattern::new).collect())
+            .unwrap_or_default(),
+        MasterlistRef::Pack(pattern) => vec![PackPattern::new(pattern.clone())],
+    }
+}
+
+// masterlist rules are rebuilt on every load and never edited, so they get no id
+fn rule(kind: RuleKind) -> LoadOrderRule {
+    LoadOrderRule {
+        id: uuid::Uuid::nil(),
+        kind,
+    }
+}
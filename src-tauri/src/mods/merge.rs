@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+
+use crate::stores::games::{Profile, ProfileModInfo};
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MergeStrategy {
+    /// Every mod of both profiles in the target's order, enabled if either
+    /// profile enables it.
+    Union,
+    /// Only the mods found in both profiles, enabled if both enable it.
+    Intersection,
+    /// Every mod of both profiles, the source's order and enabled state win.
+    PreferSourceOrder,
+    /// Every mod of both profiles, the target's order and enabled state win.
+    PreferTargetOrder,
+}
+
+/// Merge the mods of `source` into the ones of `target`. Mods only found in the
+/// secondary profile are placed right after the mod preceding them there.
+/// Group links of shared mods are combined and only the links to `groups`, the
+/// groups the merged profile links, are kept. A mod left without links is dropped
+/// unless one of the profiles added it by hand.
+pub(crate) fn merge_mods(
+    source: &Profile,
+    target: &Profile,
+    strategy: MergeStrategy,
+    groups: &[uuid::Uuid],
+) -> Vec<ProfileModInfo> {
+    let source_mods: HashMap<&str, &ProfileModInfo> =
+        source.mods.iter().map(|m| (m.name.as_str(), m)).collect();
+    let target_mods: HashMap<&str, &ProfileModInfo> =
+        target.mods.iter().map(|m| (m.name.as_str(), m)).collect();
+
+    let (primary, secondary) = match strategy {
+        MergeStrategy::PreferSourceOrder => (source, target),
+        _ => (target, source),
+    };
+
+    let mut names: Vec<&str> = in_load_order(primary);
+
+    if strategy == MergeStrategy::Intersection {
+        names.retain(|name| source_mods.contains_key(name));
+    } else {
+        let mut insert_at = 0;
+        for name in in_load_order(secondary) {
+            match names.iter().position(|n| *n == name) {
+                Some(pos) => insert_at = pos + 1,
+                None => {
+                    names.insert(insert_at, name);
+                    insert_at += 1;
+                }
+            }
+        }
+    }
+
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let s = source_mods.get(name);
+            let t = target_mods.get(name);
+
+            let enabled = match (s, t) {
+                (Some(s), Some(t)) => match strategy {
+                    MergeStrategy::Union => s.enabled || t.enabled,
+                    MergeStrategy::Intersection => s.enabled && t.enabled,
+                    MergeStrategy::PreferSourceOrder => s.enabled,
+                    MergeStrategy::PreferTargetOrder => t.enabled,
+                },
+                (Some(m), None) | (None, Some(m)) => m.enabled,
+                (None, None) => false,
+            };
+
+            let mut links: Vec<uuid::Uuid> = vec![];
+            for m in [t, s].into_iter().flatten() {
+                for group in m.groups.iter().flatten() {
+                    if groups.contains(group) && !links.contains(group) {
+                        links.push(*group);
+                    }
+                }
+            }
+
+            let added_by_hand = [t, s].into_iter().flatten().any(|m| m.groups.is_none());
+            if links.is_empty() && !added_by_hand {
+                return None;
+            }
+
+            Some(ProfileModInfo {
+                name: name.to_owned(),
+                enabled,
+                groups: (!links.is_empty()).then_some(links),
+                order: 0,
+            })
+        })
+        .enumerate()
+        .map(|(i, mut m)| {
+            m.order = u32::try_from(i + 1).unwrap_or(0);
+            m
+        })
+        .collect()
+}
+
+/// Groups linked by the merged profile: the ones both profiles link for an
+/// intersection, every one of them otherwise.
+pub(crate) fn merge_groups(
+    source: &Profile,
+    target: &Profile,
+    strategy: MergeStrategy,
+) -> Vec<uuid::Uuid> {
+    let mut groups = target.groups.clone();
+
+    match strategy {
+        MergeStrategy::Intersection => groups.retain(|g| source.groups.contains(g)),
+        _ => {
+            for group in &source.groups {
+                if !groups.contains(group) {
+                    groups.push(*group);
+                }
+            }
+        }
+    }
+
+    groups
+}
+
+fn in_load_order(profile: &Profile) -> Vec<&str> {
+    let mut mods: Vec<&ProfileModInfo> = profile.mods.iter().collect();
+    mods.sort_by_key(|m| m.order);
+    mods.into_iter().map(|m| m.name.as_str()).collect()
+}
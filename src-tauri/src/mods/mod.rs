@@ -1,3 +1,14 @@
+pub mod cache;
+pub mod conflicts;
+pub mod diff;
+pub mod import;
+pub mod masterlist;
+pub mod merge;
 pub mod pack;
+pub mod rules;
+pub mod scan;
+pub mod schema;
+pub mod share;
 pub mod sort;
+pub mod validation;
 pub mod writer;
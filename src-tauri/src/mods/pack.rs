@@ -1,4 +1,12 @@
-use crate::{join_path, mods::sort, supported_games::SupportedGames};
+use crate::{
+    join_path,
+    mods::{
+        cache::PackCache,
+        scan::{self, PackJob},
+        sort,
+    },
+    supported_games::SupportedGames,
+};
 use rpfm_lib::{
     files::pack::Pack,
     games::{GameInfo, manifest},
@@ -6,7 +14,7 @@ use rpfm_lib::{
 };
 use std::{
     collections::{HashMap, HashSet},
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
 #[derive(Debug, Clone)]
@@ -33,8 +41,10 @@ impl ModPack {
             .ok()
             .map(|t| (chrono::DateTime::<chrono::Utc>::from(t)).to_rfc3339());
 
-        // read dependencies now (silent failure returns empty vec)
-        let dependencies = Self::get_dependencies(game_id, path);
+        // read dependencies now (a pack that can't be read has none), unless the pack
+        // hasn't changed since it was last parsed
+        let dependencies =
+            PackCache::dependencies(path, &metadata, || Self::get_dependencies(game_id, path));
 
         Self {
             name,
@@ -46,13 +56,33 @@ impl ModPack {
         }
     }
 
-    fn get_dependencies(game_id: SupportedGames, path: &PathBuf) -> Vec<(bool, String)> {
+    /// The published file id of a Workshop pack, which is the name of the folder
+    /// Steam puts it in.
+    pub fn workshop_id(&self) -> Option<u64> {
+        if !self.from_steam_workshop {
+            return None;
+        }
+
+        self.path
+            .parent()
+            .and_then(|dir| dir.file_name())
+            .and_then(|dir| dir.to_str())
+            .and_then(|dir| dir.parse().ok())
+    }
+
+    fn get_dependencies(game_id: SupportedGames, path: &PathBuf) -> Option<Vec<(bool, String)>> {
+        Self::read_pack(game_id, path).map(|pack_file| pack_file.dependencies().to_vec())
+    }
+
+    /// Open a single pack with rpfm_lib (lazy loaded, so file data isn't read until
+    /// requested). Failures are logged and return `None`.
+    pub fn read_pack(game_id: SupportedGames, path: &Path) -> Option<Pack> {
         let Ok(game_info) = GameInfo::game_by_steam_id(game_id.into()) else {
             log::warn!(
                 "Failed to find game info for mod pack at path {:?}: game not found",
                 path
             );
-            return vec![];
+            return None;
         };
 
         let Ok(pack_file) =
@@ -62,15 +92,15 @@ impl ModPack {
                 "Failed to read mod pack file at path {:?}: file is not a valid pack",
                 path
             );
-            return vec![];
+            return None;
         };
-        pack_file.dependencies().to_vec()
+
+        Some(pack_file)
     }
 
-    pub fn retrieve_loose_mods(
-        game_id: SupportedGames,
+    pub fn find_loose_packs(
         game_mods_path: &PathBuf,
-    ) -> Result<Vec<ModPack>, rpfm_lib::error::RLibError> {
+    ) -> Result<Vec<PackJob>, rpfm_lib::error::RLibError> {
         let manifest_path = join_path!(game_mods_path, "manifest.txt");
         let manifest: Result<manifest::Manifest, rpfm_lib::error::RLibError> =
             manifest::Manifest::read(&manifest_path);
@@ -107,16 +137,19 @@ impl ModPack {
         Ok(mods
             .iter()
             .filter_map(|path| match path.extension().and_then(|ext| ext.to_str()) {
-                Some("pack") => Some(ModPack::new(game_id, path, None, false)),
+                Some("pack") => Some(PackJob {
+                    path: path.clone(),
+                    image: None,
+                    from_steam_workshop: false,
+                }),
                 _ => None,
             })
             .collect())
     }
 
-    pub fn retrieve_workshop_mods(
-        steam_workshop_folder: &PathBuf,
-        game_id: SupportedGames, // TODO: we should be the one to actually build the path... somewhere!
-    ) -> Result<Vec<ModPack>, rpfm_lib::error::RLibError> {
+    pub fn find_workshop_packs(
+        steam_workshop_folder: &PathBuf, // TODO: we should be the one to actually build the path... somewhere!
+    ) -> Result<Vec<PackJob>, rpfm_lib::error::RLibError> {
         //let workshop_path = join_path!(steam_workshop_folder, game_id);
         let workshop_files: Vec<PathBuf> = match files_from_subdir(steam_workshop_folder, true) {
             Ok(files) => files,
@@ -157,7 +190,11 @@ impl ModPack {
             .into_values()
             .filter_map(|(pack_path, image_path)| {
                 if let Some(pack_path) = pack_path {
-                    return Some(ModPack::new(game_id, pack_path, image_path, true));
+                    return Some(PackJob {
+                        path: pack_path.clone(),
+                        image: image_path.cloned(),
+                        from_steam_workshop: true,
+                    });
                 }
 
                 None
@@ -167,7 +204,7 @@ impl ModPack {
 
     /// Lightweight scan of the workshop folder: returns `(pack_name, workshop_id)`
     /// for every `.pack` file, where `workshop_id` is the numeric parent directory
-    /// (the published file id). Unlike [`retrieve_workshop_mods`], this does not
+    /// (the published file id). Unlike [`retrieve_mods`], this does not
     /// parse pack contents, so it is cheap to run on every load.
     pub fn scan_workshop_ids(steam_workshop_folder: &PathBuf) -> Vec<(String, u64)> {
         let files = match files_from_subdir(steam_workshop_folder, true) {
@@ -202,26 +239,44 @@ impl ModPack {
         game_mods_path: &PathBuf,
         steam_workshop_path: &Option<PathBuf>,
     ) -> Vec<ModPack> {
-        // merge the workshop and loose mods
-        let loose_mods = Self::retrieve_loose_mods(game_id, game_mods_path).ok();
-        let workshop_mods = match steam_workshop_path {
-            Some(path) => Self::retrieve_workshop_mods(path, game_id).ok(), // TODO: we should be the one to actually build the path... somewhere!
+        // merge the workshop and loose mods, then parse them all in a single scan
+        let loose_packs = Self::find_loose_packs(game_mods_path).ok();
+        let workshop_packs = match steam_workshop_path {
+            Some(path) => Self::find_workshop_packs(path).ok(), // TODO: we should be the one to actually build the path... somewhere!
             None => Some(vec![]),
         };
 
-        let mut mods = vec![];
+        let mut jobs = vec![];
 
-        if let Some(data_mods) = loose_mods {
-            mods.extend(data_mods);
+        if let Some(data_packs) = loose_packs {
+            jobs.extend(data_packs);
         }
 
-        if let Some(workshop_mods) = workshop_mods {
-            mods.extend(workshop_mods);
+        if let Some(workshop_packs) = workshop_packs {
+            jobs.extend(workshop_packs);
         }
 
+        let mods = scan::parse_packs(game_id, jobs);
+
+        PackCache::flush();
+
         mods
     }
 
+    /// Pack names of this pack's dependencies, as they appear in profiles.
+    pub fn dependency_names(&self) -> Vec<String> {
+        self.dependencies
+            .iter()
+            .map(|(_, dependency)| Self::dependency_name(dependency).to_owned())
+            .collect()
+    }
+
+    /// Dependencies are stored as file names (`name.pack`), while mods are
+    /// referenced by their file stem everywhere else.
+    pub fn dependency_name(dependency: &str) -> &str {
+        dependency.strip_suffix(".pack").unwrap_or(dependency)
+    }
+
     pub fn sort(mods: &mut [ModPack]) {
         // mutates the original vector to avoid cloning and preserve references
         mods.sort_by(|a, b| sort::compare_mod_names(&a.name, &b.name));
@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// A pack name, or a name pattern where `*` matches any run of characters and
+/// `?` a single one. Matching ignores case and the `.pack` extension.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PackPattern(String);
+
+impl PackPattern {
+    pub fn new(pattern: String) -> Self {
+        Self(pattern)
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        let pattern: Vec<char> = normalize(&self.0).chars().collect();
+        let name: Vec<char> = normalize(name).chars().collect();
+
+        wildcard_match(&pattern, &name)
+    }
+
+    fn is_blank(&self) -> bool {
+        normalize(&self.0).is_empty()
+    }
+}
+
+/// "Loaded after" means later in the mod list, the way a pack's dependencies are
+/// loaded before it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum RuleKind {
+    LoadAfter {
+        pack: PackPattern,
+        after: PackPattern,
+    },
+    LoadBefore {
+        pack: PackPattern,
+        before: PackPattern,
+    },
+    /// Flagged by the validation when both sides are enabled.
+    Incompatible {
+        pack: PackPattern,
+        with: PackPattern,
+    },
+    /// After every pack not matched by an `AlwaysLast` rule itself.
+    AlwaysLast { pack: PackPattern },
+}
+
+/// A user-authored load order rule, stored per game and applied to the
+/// automatic order of every profile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadOrderRule {
+    #[serde(default = "uuid::Uuid::new_v4")]
+    pub id: uuid::Uuid,
+    #[serde(flatten)]
+    pub kind: RuleKind,
+}
+
+impl LoadOrderRule {
+    pub fn validate(&self) -> Result<(), String> {
+        let (pack, other) = match &self.kind {
+            RuleKind::LoadAfter { pack, after: other }
+            | RuleKind::LoadBefore {
+                pack,
+                before: other,
+            }
+            | RuleKind::Incompatible { pack, with: other } => (pack, Some(other)),
+            RuleKind::AlwaysLast { pack } => (pack, None),
+        };
+
+        if pack.is_blank() || other.is_some_and(PackPattern::is_blank) {
+            return Err(format!("rule {} has an empty pack pattern", self.id));
+        }
+
+        if other.is_some_and(|other| normalize(&other.0) == normalize(&pack.0)) {
+            return Err(format!(
+                "rule {} targets the same packs on both sides",
+                self.id
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// For every name matched by an ordering rule, the names the rules want
+/// loaded before it.
+pub fn load_after_map(rules: &[LoadOrderRule], names: &[&str]) -> HashMap<String, Vec<String>> {
+    let mut load_after: HashMap<String, Vec<String>> = HashMap::new();
+    let mut always_last: Vec<&PackPattern> = vec![];
+
+    for rule in rules {
+        match &rule.kind {
+            RuleKind::LoadAfter { pack, after } => add_edges(&mut load_after, names, pack, after),
+            RuleKind::LoadBefore { pack, before } => {
+                add_edges(&mut load_after, names, before, pack)
+            }
+            RuleKind::AlwaysLast { pack } => always_last.push(pack),
+            RuleKind::Incompatible { .. } => {}
+        }
+    }
+
+    let (last, rest): (Vec<&str>, Vec<&str>) = names
+        .iter()
+        .copied()
+        .partition(|name| always_last.iter().any(|p| p.matches(name)));
+
+    for name in last {
+        load_after
+            .entry(name.to_owned())
+            .or_default()
+            .extend(rest.iter().map(|n| n.to_string()));
+    }
+
+    load_after
+}
+
+/// The pairs of names the rules mark as incompatible, each pair reported once.
+pub fn incompatible_pairs<'a>(
+    rules: &[LoadOrderRule],
+    names: &[&'a str],
+) -> Vec<(&'a str, &'a str)> {
+    let mut pairs: Vec<(&str, &str)> = vec![];
+
+    for rule in rules {
+        let RuleKind::Incompatible { pack, with } = &rule.kind else {
+            continue;
+        };
+
+        for a in names.iter().filter(|n| pack.matches(n)) {
+            for b in names.iter().filter(|n| with.matches(n) && *n != a) {
+                let pair = if a <= b { (*a, *b) } else { (*b, *a) };
+                if !pairs.contains(&pair) {
+                    pairs.push(pair);
+                }
+            }
+        }
+    }
+
+    pairs
+}
+
+fn add_edges(
+    load_after: &mut HashMap<String, Vec<String>>,
+    names: &[&str],
+    later: &PackPattern,
+    earlier: &PackPattern,
+) {
+    for name in names.iter().filter(|n| later.matches(n)) {
+        let before = names
+            .iter()
+            .filter(|n| earlier.matches(n) && *n != name)
+            .map(|n| n.to_string());
+
+        load_after
+            .entry(name.to_string())
+            .or_default()
+            .extend(before);
+    }
+}
+
+fn normalize(name: &str) -> String {
+    let name = name.trim().to_lowercase();
+
+    name.strip_suffix(".pack")
+        .map(str::to_owned)
+        .unwrap_or(name)
+}
+
+// `*` and `?` wildcards, backtracking to the last `*` on a mismatch
+fn wildcard_match(pattern: &[char], name: &[char]) -> bool {
+    let (mut p, mut n) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    star = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
@@ -0,0 +1,122 @@
+use std::{
+    cell::Cell,
+    path::PathBuf,
+    sync::{
+        Mutex, OnceLock,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use tauri::{AppHandle, Emitter};
+
+use crate::{events::AppEvent, mods::pack::ModPack, supported_games::SupportedGames};
+
+// Scans are started from places that don't carry an app handle (store defaults,
+// the mod writer...), so the one used to report progress is registered at setup.
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+thread_local! {
+    // set by `with_progress` for the scans the user is waiting on
+    static REPORT_PROGRESS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// A pack found on disk, waiting to be parsed.
+#[derive(Debug, Clone)]
+pub struct PackJob {
+    pub path: PathBuf,
+    pub image: Option<PathBuf>,
+    pub from_steam_workshop: bool,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ScanProgress {
+    done: usize,
+    total: usize,
+}
+
+pub fn init(app_handle: AppHandle) {
+    let _ = APP_HANDLE.set(app_handle);
+}
+
+/// Run `f`, reporting the progress of the scans it starts on this thread. Only
+/// scans the user started report progress; the ones run by background tasks
+/// (save links, Workshop names...) stay silent. `f` must not yield to the async
+/// runtime, the flag is per thread.
+pub fn with_progress<R>(f: impl FnOnce() -> R) -> R {
+    let previous = REPORT_PROGRESS.replace(true);
+    let result = f();
+    REPORT_PROGRESS.set(previous);
+    result
+}
+
+/// Parse every pack on a pool of worker threads, emitting `scan/progress` after
+/// each one when called within [`with_progress`]. The result is sorted by pack
+/// path, whatever order the workers finish in.
+pub(crate) fn parse_packs(game_id: SupportedGames, mut jobs: Vec<PackJob>) -> Vec<ModPack> {
+    jobs.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let total = jobs.len();
+    if total == 0 {
+        return vec![];
+    }
+
+    let workers = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(total);
+
+    log::debug!("Scanning {} pack(s) on {} worker(s)", total, workers);
+
+    let report_progress = REPORT_PROGRESS.get();
+
+    let next = AtomicUsize::new(0);
+    let done = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<ModPack>>> = Mutex::new(vec![None; total]);
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(job) = jobs.get(index) else {
+                        break;
+                    };
+
+                    let pack = ModPack::new(
+                        game_id,
+                        &job.path,
+                        job.image.as_ref(),
+                        job.from_steam_workshop,
+                    );
+
+                    if let Ok(mut results) = results.lock() {
+                        results[index] = Some(pack);
+                    }
+
+                    let done = done.fetch_add(1, Ordering::Relaxed) + 1;
+                    if report_progress {
+                        emit_progress(done, total);
+                    }
+                }
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap_or_default()
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+fn emit_progress(done: usize, total: usize) {
+    let Some(app_handle) = APP_HANDLE.get() else {
+        return;
+    };
+
+    if let Err(e) = app_handle.emit(AppEvent::ScanProgress.into(), ScanProgress { done, total }) {
+        log::warn!("Failed to emit scan progress: {:?}", e);
+    }
+}
@@ -0,0 +1,87 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, OnceLock},
+    time::Duration,
+};
+
+use rpfm_lib::{games::GameInfo, schema::Schema};
+use tauri::Manager;
+
+use crate::supported_games::SupportedGames;
+
+// The schemas RPFM itself uses, one file per game.
+const SCHEMAS_URL: &str = "https://raw.githubusercontent.com/Frodo45127/rpfm-schemas/master";
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(60);
+
+// Schemas are large and slow to parse, so a loaded one is kept for the session.
+static SCHEMAS: OnceLock<Mutex<HashMap<String, Arc<Schema>>>> = OnceLock::new();
+
+/// The table schema of the game, needed to decode DB tables. It's downloaded on
+/// first use and read from disk afterwards; `None` when it can't be had (offline
+/// on first use, for example).
+pub fn load(app_handle: &tauri::AppHandle, game_id: SupportedGames) -> Option<Arc<Schema>> {
+    let game_info = GameInfo::game_by_steam_id(game_id.into()).ok()?;
+    let file_name = game_info.schema_file_name().to_owned();
+
+    let schemas = SCHEMAS.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(schema) = schemas.lock().unwrap().get(&file_name) {
+        return Some(schema.clone());
+    }
+
+    let schema = match read(app_handle, &file_name) {
+        Ok(schema) => Arc::new(schema),
+        Err(e) => {
+            log::warn!("Table schema {} unavailable: {:?}", file_name, e);
+            return None;
+        }
+    };
+
+    schemas.lock().unwrap().insert(file_name, schema.clone());
+
+    Some(schema)
+}
+
+fn read(app_handle: &tauri::AppHandle, file_name: &str) -> Result<Schema, Box<dyn Error>> {
+    let path = schema_path(app_handle, file_name)?;
+
+    if !path.exists() {
+        download(file_name, &path)?;
+    }
+
+    Ok(Schema::load(&path, None)?)
+}
+
+fn schema_path(app_handle: &tauri::AppHandle, file_name: &str) -> Result<PathBuf, Box<dyn Error>> {
+    Ok(app_handle
+        .path()
+        .app_data_dir()?
+        .join("schemas")
+        .join(file_name))
+}
+
+// Written through a temporary file, so an interrupted download isn't mistaken for
+// the schema next time.
+fn download(file_name: &str, path: &Path) -> Result<(), Box<dyn Error>> {
+    let url = format!("{}/{}", SCHEMAS_URL, file_name);
+    log::info!("Downloading table schema from {}", url);
+
+    let mut res = ureq::get(&url)
+        .config()
+        .timeout_global(Some(DOWNLOAD_TIMEOUT))
+        .build()
+        .call()?;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let tmp_path = path.with_extension("ron.tmp");
+    let mut file = std::fs::File::create(&tmp_path)?;
+    std::io::copy(&mut res.body_mut().as_reader(), &mut file)?;
+    file.sync_all()?;
+    std::fs::rename(&tmp_path, path)?;
+
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+
+use crate::{
+    mods::import::ImportedMod,
+    stores::games::{GameStore, Profile},
+    supported_games::SupportedGames,
+};
+
+/// Bumped whenever `ProfileExport` changes in a way older versions can't read.
+pub const PROFILE_EXPORT_VERSION: u32 = 1;
+
+// Share codes are the export as base64 JSON behind this prefix, so they can be
+// told apart from a pasted export file.
+const SHARE_CODE_PREFIX: &str = "fh1:";
+
+/// A profile in a form that can be moved between machines: everything refers to
+/// mods and groups by name instead of local ids and paths.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileExport {
+    pub version: u32,
+    pub game_id: SupportedGames,
+    pub name: String,
+    pub manual_mode: bool,
+    pub mods: Vec<ExportedMod>,
+    pub groups: Vec<ExportedGroup>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedMod {
+    pub name: String,
+    pub enabled: bool,
+    pub order: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workshop_id: Option<u64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<String>, // names of the groups linking this mod
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedGroup {
+    pub name: String,
+    pub mods: Vec<String>,
+}
+
+impl ProfileExport {
+    /// `workshop_ids` maps pack names to their published file id, as returned by
+    /// `ModPack::scan_workshop_ids`.
+    pub fn new(game: &GameStore, profile: &Profile, workshop_ids: &HashMap<String, u64>) -> Self {
+        let group_name = |id: &uuid::Uuid| {
+            game.groups
+                .iter()
+                .find(|g| &g.id == id)
+                .map(|g| g.name.clone())
+        };
+
+        let mut mods: Vec<ExportedMod> = profile
+            .mods
+            .iter()
+            .map(|m| ExportedMod {
+                name: m.name.clone(),
+                enabled: m.enabled,
+                order: m.order,
+                custom_name: game.mod_custom_names.get(&m.name).cloned(),
+                workshop_id: workshop_ids.get(&m.name).copied(),
+                groups: m.groups.iter().flatten().filter_map(&group_name).collect(),
+            })
+            .collect();
+        mods.sort_by_key(|m| m.order);
+
+        let groups = game
+            .groups
+            .iter()
+            .filter(|g| profile.groups.contains(&g.id))
+            .map(|g| ExportedGroup {
+                name: g.name.clone(),
+                mods: g.mods.clone(),
+            })
+            .collect();
+
+        Self {
+            version: PROFILE_EXPORT_VERSION,
+            game_id: game.game_id,
+            name: profile.name.clone(),
+            manual_mode: profile.manual_mode,
+            mods,
+            groups,
+        }
+    }
+
+    pub fn to_share_code(&self) -> Result<String, serde_json::Error> {
+        let json = serde_json::to_vec(self)?;
+        Ok(format!(
+            "{}{}",
+            SHARE_CODE_PREFIX,
+            URL_SAFE_NO_PAD.encode(json)
+        ))
+    }
+
+    /// Parse either a share code or the JSON export itself.
+    pub fn parse(input: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let input = input.trim();
+
+        let export: Self = match input.strip_prefix(SHARE_CODE_PREFIX) {
+            Some(code) => serde_json::from_slice(&URL_SAFE_NO_PAD.decode(code)?)?,
+            None => serde_json::from_str(input)?,
+        };
+
+        if export.version > PROFILE_EXPORT_VERSION {
+            return Err(format!(
+                "Profile export version {} is newer than the supported version {}",
+                export.version, PROFILE_EXPORT_VERSION
+            )
+            .into());
+        }
+
+        Ok(export)
+    }
+
+    /// The exported mods in load order, ready for `import::resolve_mods`.
+    pub fn imported_mods(&self) -> Vec<ImportedMod> {
+        self.mods
+            .iter()
+            .map(|m| ImportedMod {
+                name: m.name.clone(),
+                enabled: m.enabled,
+            })
+            .collect()
+    }
+}
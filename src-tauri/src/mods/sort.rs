@@ -1,3 +1,5 @@
+use std::collections::{BTreeSet, HashMap};
+
 pub trait SortMods<T> {
     fn sort_mods<F>(&mut self, key_fn: F)
     where
@@ -39,3 +41,114 @@ pub fn compare_mod_names(a: &str, b: &str) -> std::cmp::Ordering {
 
     std::cmp::Ordering::Equal
 }
+
+/// Reorders `items` so that every item comes after the items it depends on, using
+/// `compare_mod_names` to break ties between items that are free to go in either
+/// order. Dependencies that don't match any item are ignored.
+///
+/// If the dependencies contain a cycle, the items that could be placed are still
+/// sorted, the rest are appended in name order and the names forming the cycle
+/// are returned as the error.
+pub fn sort_by_dependencies<T, K, D>(
+    items: &mut Vec<T>,
+    key_fn: K,
+    mut deps_fn: D,
+) -> Result<(), Vec<String>>
+where
+    K: Fn(&T) -> &str,
+    D: FnMut(&T) -> Vec<String>,
+{
+    items.sort_mods(&key_fn);
+
+    let index: HashMap<String, usize> = items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| (key_fn(item).to_owned(), i))
+        .collect();
+
+    // dependencies[i] = items that have to be placed before items[i]
+    let dependencies: Vec<Vec<usize>> = items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let mut deps: Vec<usize> = deps_fn(item)
+                .iter()
+                .filter_map(|name| index.get(name).copied())
+                .filter(|dep| *dep != i)
+                .collect();
+            deps.sort_unstable();
+            deps.dedup();
+            deps
+        })
+        .collect();
+
+    let mut dependents: Vec<Vec<usize>> = vec![vec![]; items.len()];
+    let mut pending: Vec<usize> = vec![0; items.len()];
+
+    for (i, deps) in dependencies.iter().enumerate() {
+        pending[i] = deps.len();
+        for dep in deps {
+            dependents[*dep].push(i);
+        }
+    }
+
+    // items are already in name order, so the smallest ready index is the tie-breaker
+    let mut ready: BTreeSet<usize> = (0..items.len()).filter(|i| pending[*i] == 0).collect();
+    let mut order: Vec<usize> = Vec::with_capacity(items.len());
+
+    while let Some(i) = ready.pop_first() {
+        order.push(i);
+
+        for dependent in &dependents[i] {
+            pending[*dependent] -= 1;
+            if pending[*dependent] == 0 {
+                ready.insert(*dependent);
+            }
+        }
+    }
+
+    let result = if order.len() < items.len() {
+        let cycle = find_cycle(&dependencies, &pending)
+            .into_iter()
+            .map(|i| key_fn(&items[i]).to_owned())
+            .collect();
+
+        order.extend((0..items.len()).filter(|i| pending[*i] > 0));
+        Err(cycle)
+    } else {
+        Ok(())
+    };
+
+    let mut slots: Vec<Option<T>> = items.drain(..).map(Some).collect();
+    items.extend(order.into_iter().filter_map(|i| slots[i].take()));
+
+    result
+}
+
+// Every unplaced item is either part of a cycle or depends on one, so following
+// unplaced dependencies from any of them must eventually revisit an item.
+fn find_cycle(dependencies: &[Vec<usize>], pending: &[usize]) -> Vec<usize> {
+    let Some(start) = (0..pending.len()).find(|i| pending[*i] > 0) else {
+        return vec![];
+    };
+
+    let mut path: Vec<usize> = vec![start];
+    let mut current = start;
+
+    loop {
+        let Some(next) = dependencies[current]
+            .iter()
+            .copied()
+            .find(|dep| pending[*dep] > 0)
+        else {
+            return path;
+        };
+
+        if let Some(pos) = path.iter().position(|i| *i == next) {
+            return path.split_off(pos);
+        }
+
+        path.push(next);
+        current = next;
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    dto::{
+        games::GameResponseDto,
+        mods::ModResponseDto,
+        packs::PackResponseDto,
+        validation::{ValidationProblemDto, ValidationProblemKind},
+    },
+    mods::{
+        masterlist::MasterlistRequirement,
+        pack::ModPack,
+        rules::{self, LoadOrderRule},
+    },
+    stores::games::{Profile, ProfileModInfo},
+};
+
+/// What `start_game` does when the pre-launch validation finds problems.
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DependencyPolicy {
+    /// Log the problems and launch anyway.
+    #[default]
+    Warn,
+    /// Refuse to launch.
+    Block,
+    /// Enable the dependencies found on disk, then launch.
+    AutoEnable,
+}
+
+/// Every check below: the packs' own dependencies, the incompatibilities from
+/// the game's rules and masterlist, and the masterlist's requirements.
+pub(crate) fn validate_profile_mods(
+    profile_mods: &[ModResponseDto],
+    game: &GameResponseDto,
+) -> Vec<ValidationProblemDto> {
+    let mut problems = validate_dependencies(profile_mods, &game.mods);
+    problems.extend(validate_rules(profile_mods, &game.load_order_rules));
+
+    if let Some(masterlist) = &game.masterlist {
+        problems.extend(validate_rules(profile_mods, &masterlist.rules));
+        problems.extend(validate_requirements(
+            profile_mods,
+            &game.mods,
+            &masterlist.requirements,
+        ));
+    }
+
+    problems
+}
+
+/// Checks the dependencies of every enabled mod in a profile against the profile
+/// itself and the packs found on disk.
+pub(crate) fn validate_dependencies(
+    profile_mods: &[ModResponseDto],
+    disk_mods: &[PackResponseDto],
+) -> Vec<ValidationProblemDto> {
+    let profile: HashMap<&str, &ModResponseDto> =
+        profile_mods.iter().map(|m| (m.name.as_str(), m)).collect();
+    let on_disk: HashSet<&str> = disk_mods.iter().map(|p| p.name.as_str()).collect();
+
+    let mut problems = vec![];
+
+    for m in profile_mods.iter().filter(|m| m.enabled) {
+        let Some(dependencies) = &m.dependencies else {
+            continue;
+        };
+
+        for (hard, dependency) in dependencies {
+            let target = ModPack::dependency_name(dependency);
+
+            let Some(kind) = unmet_dependency(m, target, &profile, &on_disk) else {
+                continue;
+            };
+
+            problems.push(ValidationProblemDto {
+                kind,
+                mod_name: m.name.clone(),
+                target: target.to_owned(),
+                hard: *hard,
+            });
+        }
+    }
+
+    problems
+}
+
+/// Checks the masterlist's requirements of every enabled mod like the packs' own
+/// dependencies. A requirement listing several packs (a Workshop item shipping
+/// more than one) is met by any of them.
+pub(crate) fn validate_requirements(
+    profile_mods: &[ModResponseDto],
+    disk_mods: &[PackResponseDto],
+    requirements: &[MasterlistRequirement],
+) -> Vec<ValidationProblemDto> {
+    let profile: HashMap<&str, &ModResponseDto> =
+        profile_mods.iter().map(|m| (m.name.as_str(), m)).collect();
+    let on_disk: HashSet<&str> = disk_mods.iter().map(|p| p.name.as_str()).collect();
+
+    let mut problems = vec![];
+
+    for m in profile_mods.iter().filter(|m| m.enabled) {
+        for requirement in requirements.iter().filter(|r| r.pack.matches(&m.name)) {
+            let mut unmet = vec![];
+
+            for target in requirement.requires.iter().filter(|t| **t != m.name) {
+                match unmet_dependency(m, target, &profile, &on_disk) {
+                    Some(kind) => unmet.push((kind, target)),
+                    None => {
+                        unmet.clear();
+                        break;
+                    }
+                }
+            }
+
+            if let Some((kind, target)) = unmet.into_iter().next() {
+                problems.push(ValidationProblemDto {
+                    kind,
+                    mod_name: m.name.clone(),
+                    target: target.clone(),
+                    hard: true,
+                });
+            }
+        }
+    }
+
+    problems
+}
+
+/// Flags the enabled mods that a load order rule marks as incompatible with
+/// each other.
+pub(crate) fn validate_rules(
+    profile_mods: &[ModResponseDto],
+    load_order_rules: &[LoadOrderRule],
+) -> Vec<ValidationProblemDto> {
+    let enabled: Vec<&str> = profile_mods
+        .iter()
+        .filter(|m| m.enabled)
+        .map(|m| m.name.as_str())
+        .collect();
+
+    rules::incompatible_pairs(load_order_rules, &enabled)
+        .into_iter()
+        .map(|(mod_name, target)| ValidationProblemDto {
+            kind: ValidationProblemKind::Incompatible,
+            mod_name: mod_name.to_owned(),
+            target: target.to_owned(),
+            hard: true,
+        })
+        .collect()
+}
+
+/// Enables the dependencies reported as disabled, adding them to the profile when
+/// they aren't part of it yet. Returns how many mods were enabled.
+pub(crate) fn enable_dependencies(
+    profile: &mut Profile,
+    problems: &[ValidationProblemDto],
+) -> usize {
+    let mut enabled = 0;
+
+    for problem in problems
+        .iter()
+        .filter(|p| p.kind == ValidationProblemKind::DisabledDependency)
+    {
+        match profile.mods.iter_mut().find(|m| m.name == problem.target) {
+            Some(existing) if existing.enabled => continue,
+            Some(existing) => existing.enabled = true,
+            None => profile.mods.push(ProfileModInfo {
+                name: problem.target.clone(),
+                enabled: true,
+                groups: None,
+                order: 0, // front of the list, ahead of the mods needing it
+            }),
+        }
+
+        log::info!(
+            "Enabled '{}' in profile '{}' (required by '{}')",
+            problem.target,
+            profile.name,
+            problem.mod_name
+        );
+        enabled += 1;
+    }
+
+    enabled
+}
+
+// What's wrong with `m` depending on `target`, if anything.
+fn unmet_dependency(
+    m: &ModResponseDto,
+    target: &str,
+    profile: &HashMap<&str, &ModResponseDto>,
+    on_disk: &HashSet<&str>,
+) -> Option<ValidationProblemKind> {
+    match profile.get(target) {
+        Some(dep) if dep.enabled => {
+            (dep.order > m.order).then_some(ValidationProblemKind::DependencyLoadedAfter)
+        }
+        Some(dep) if dep.can_enable => Some(ValidationProblemKind::DisabledDependency),
+        None if on_disk.contains(target) => Some(ValidationProblemKind::DisabledDependency),
+        _ => Some(ValidationProblemKind::MissingDependency),
+    }
+}
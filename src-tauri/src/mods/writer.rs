@@ -1,7 +1,64 @@
-use std::{fs::File, io::Write, path::PathBuf};
+use std::{
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
 
 use crate::{dto::mods::ModResponseDto, mods::pack::ModPack, supported_games::SupportedGames};
 
+// Every profile gets its own script in the game folder, so launches of different
+// profiles (or other tools using `used_mods.txt`) don't overwrite each other.
+const SCRIPT_PREFIX: &str = "foolhammer_";
+const SCRIPT_EXTENSION: &str = "txt";
+const PARTIAL_EXTENSION: &str = "partial";
+
+/// The name of the mod list script written for a profile, relative to the game folder.
+pub fn script_file_name(profile_id: uuid::Uuid) -> String {
+    format!(
+        "{}{}.{}",
+        SCRIPT_PREFIX,
+        profile_id.simple(),
+        SCRIPT_EXTENSION
+    )
+}
+
+/// Remove the scripts of profiles that no longer exist and leftovers of
+/// interrupted writes. Only files following our naming are touched.
+pub fn remove_stale_scripts(game_path: &Path, profile_ids: &[uuid::Uuid]) {
+    let Ok(entries) = std::fs::read_dir(game_path) else {
+        return;
+    };
+
+    let keep: Vec<String> = profile_ids.iter().map(|id| script_file_name(*id)).collect();
+
+    for path in entries.filter_map(|e| e.ok().map(|e| e.path())) {
+        let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+
+        let Some(profile_part) = file_name
+            .strip_prefix(SCRIPT_PREFIX)
+            .and_then(|rest| rest.split('.').next())
+        else {
+            continue;
+        };
+
+        let generated = uuid::Uuid::parse_str(profile_part).is_ok()
+            && path
+                .extension()
+                .is_some_and(|ext| ext == SCRIPT_EXTENSION || ext == PARTIAL_EXTENSION);
+
+        if !generated || keep.iter().any(|k| k == file_name) {
+            continue;
+        }
+
+        match std::fs::remove_file(&path) {
+            Ok(()) => log::info!("Removed stale mod list {}", path.display()),
+            Err(e) => log::warn!("Failed to remove stale mod list {}: {}", path.display(), e),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Mod {
     pub name: String,
@@ -55,11 +112,29 @@ impl ModWriter {
         Self { mods }
     }
 
+    /// The packs that will be loaded, in load order.
+    pub fn mod_names(&self) -> Vec<String> {
+        self.mods.iter().map(|m| m.name.clone()).collect()
+    }
+
+    /// Write the script through a temporary file renamed over the previous one,
+    /// so the game never reads a half-written list.
     pub fn write(self, txt_path: PathBuf) -> Result<(), Box<dyn std::error::Error>> {
-        if txt_path.exists() {
-            std::fs::remove_file(&txt_path)?;
+        let partial_path = txt_path.with_extension(PARTIAL_EXTENSION);
+        let result = self.write_to(&partial_path).and_then(|()| {
+            std::fs::rename(&partial_path, &txt_path)?;
+            Ok(())
+        });
+
+        if result.is_err() {
+            let _ = std::fs::remove_file(&partial_path);
         }
-        let mut file = File::options().append(true).create(true).open(txt_path)?;
+
+        result
+    }
+
+    fn write_to(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let mut file = File::create(path)?;
 
         // add_working_directory "[Z: or C: on Windows]\home\<username>\.local\share\Steam\steamapps\workshop\content\<gameId>\<modId>";
         // mod "<name>.pack";
@@ -91,6 +166,8 @@ impl ModWriter {
             writeln!(&mut file, "{}", line)?;
         }
 
+        file.sync_all()?;
+
         Ok(())
     }
 }
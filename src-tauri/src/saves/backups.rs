@@ -0,0 +1,341 @@
+use std::{
+    collections::BTreeSet,
+    fs::File,
+    io::{self, Read},
+    path::{Component, Path, PathBuf},
+    sync::Mutex,
+    time::Duration,
+};
+
+use crate::{
+    dto::saves::SaveBackupDto, saves::header::SaveHeader, stores::settings::SettingsStore,
+    supported_games::SupportedGames, utils,
+};
+
+// The game writes a save in several passes, and every pass fires a watcher
+// event. A save is only copied once its size and modification time stop changing.
+const SETTLE_DELAY: Duration = Duration::from_secs(2);
+const MAX_SETTLE_CHECKS: usize = 30;
+
+// `<timestamp>__<save name>`, so a campaign folder sorts oldest first
+const BACKUP_NAME_SEPARATOR: &str = "__";
+const BACKUP_TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%S%3f";
+
+// Saves waiting to settle, so repeated events don't queue more copies.
+static PENDING_BACKUPS: Mutex<BTreeSet<PathBuf>> = Mutex::new(BTreeSet::new());
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveBackupSettings {
+    pub enabled: bool,
+    pub max_per_campaign: u32, // 0 keeps every backup
+}
+
+impl Default for SaveBackupSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_per_campaign: 10,
+        }
+    }
+}
+
+/// Called by the folder watcher when a save of the watched game is written.
+/// When backups are enabled, the save is copied once the game is done writing it.
+pub(crate) fn backup_written_save(
+    app_handle: &tauri::AppHandle,
+    game_id: SupportedGames,
+    path: &Path,
+) {
+    let Ok(store) = SettingsStore::get_store(app_handle) else {
+        return;
+    };
+    let Ok(settings) = SettingsStore::from_entries(store.entries()) else {
+        return;
+    };
+
+    if !settings.save_backups.enabled {
+        return;
+    }
+
+    if let Ok(mut pending) = PENDING_BACKUPS.lock()
+        && !pending.insert(path.to_path_buf())
+    {
+        return;
+    }
+
+    let app_handle = app_handle.clone();
+    let path = path.to_path_buf();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let settled = wait_until_settled(&path);
+
+        if let Ok(mut pending) = PENDING_BACKUPS.lock() {
+            pending.remove(&path);
+        }
+
+        if !settled {
+            return;
+        }
+
+        match backup_save(&app_handle, game_id, &path, settings.save_backups) {
+            Ok(Some(backup)) => {
+                log::info!("Backed up save {} to {}", path.display(), backup.display())
+            }
+            Ok(None) => log::debug!("Save {} already backed up", path.display()),
+            Err(e) => log::warn!("Failed to back up save {}: {:?}", path.display(), e),
+        }
+    });
+}
+
+/// Copy a save into its campaign's backup folder, unless the newest backup of
+/// that save is identical. Older backups beyond the retention limit are removed.
+pub fn backup_save(
+    app_handle: &tauri::AppHandle,
+    game_id: SupportedGames,
+    path: &Path,
+    settings: SaveBackupSettings,
+) -> io::Result<Option<PathBuf>> {
+    let save_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| io::Error::from(io::ErrorKind::InvalidInput))?;
+
+    let header = SaveHeader::read(path)
+        .inspect_err(|e| log::debug!("Couldn't read the header of {}: {}", path.display(), e))
+        .unwrap_or_default();
+    let dir = backups_root(app_handle, game_id).join(campaign_key(&header, path));
+    std::fs::create_dir_all(&dir)?;
+
+    let previous = backup_files(&dir)?
+        .into_iter()
+        .rfind(|(_, name)| name == &save_name);
+
+    if let Some((latest, _)) = previous
+        && files_equal(path, &latest)?
+    {
+        return Ok(None);
+    }
+
+    let timestamp = chrono::Utc::now().format(BACKUP_TIMESTAMP_FORMAT);
+    let target = dir.join(format!(
+        "{}{}{}",
+        timestamp, BACKUP_NAME_SEPARATOR, save_name
+    ));
+
+    // copy under a temporary name so a partial copy never shows up as a backup
+    let partial = target.with_extension("partial");
+    std::fs::copy(path, &partial)?;
+    std::fs::rename(&partial, &target)?;
+
+    prune(&dir, settings.max_per_campaign)?;
+
+    Ok(Some(target))
+}
+
+/// Every backup of the game, newest first.
+pub fn list_backups(
+    app_handle: &tauri::AppHandle,
+    game_id: SupportedGames,
+) -> io::Result<Vec<SaveBackupDto>> {
+    let root = backups_root(app_handle, game_id);
+    if !root.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut backups = vec![];
+
+    for entry in std::fs::read_dir(&root)? {
+        let campaign_dir = entry?.path();
+        if !campaign_dir.is_dir() {
+            continue;
+        }
+
+        let campaign = campaign_dir
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+
+        for (path, save_name) in backup_files(&campaign_dir)? {
+            let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+            let created_at = file_name
+                .split_once(BACKUP_NAME_SEPARATOR)
+                .and_then(|(timestamp, _)| {
+                    chrono::NaiveDateTime::parse_from_str(timestamp, BACKUP_TIMESTAMP_FORMAT).ok()
+                })
+                .map(|t| t.and_utc().to_rfc3339());
+
+            backups.push(SaveBackupDto {
+                id: format!("{}/{}", campaign, file_name),
+                campaign: campaign.clone(),
+                save_name,
+                created_at,
+                size: std::fs::metadata(&path)?.len(),
+            });
+        }
+    }
+
+    backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+    Ok(backups)
+}
+
+/// Put a backup back into the saves folder under its original name. The save it
+/// replaces is backed up first, whatever the settings, so a restore can be undone.
+pub fn restore_backup(
+    app_handle: &tauri::AppHandle,
+    game_id: SupportedGames,
+    backup_id: &str,
+    saves_path: &Path,
+) -> io::Result<PathBuf> {
+    // ids are `<campaign>/<file>`, anything else could point outside the backups
+    let components: Vec<Component> = Path::new(backup_id).components().collect();
+    if components.len() != 2 || !components.iter().all(|c| matches!(c, Component::Normal(_))) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid backup id '{}'", backup_id),
+        ));
+    }
+
+    let backup = backups_root(app_handle, game_id).join(backup_id);
+    if !backup.is_file() {
+        return Err(io::Error::from(io::ErrorKind::NotFound));
+    }
+
+    let save_name = backup
+        .file_name()
+        .and_then(|n| n.to_str())
+        .and_then(|n| n.split_once(BACKUP_NAME_SEPARATOR))
+        .map(|(_, name)| name.to_owned())
+        .ok_or_else(|| io::Error::from(io::ErrorKind::InvalidInput))?;
+
+    let target = saves_path.join(&save_name);
+
+    if target.exists() {
+        let settings = SaveBackupSettings {
+            enabled: true,
+            max_per_campaign: 0,
+        };
+        backup_save(app_handle, game_id, &target, settings)?;
+    }
+
+    let partial = target.with_extension("partial");
+    std::fs::copy(&backup, &partial)?;
+    std::fs::rename(&partial, &target)?;
+
+    Ok(target)
+}
+
+fn backups_root(app_handle: &tauri::AppHandle, game_id: SupportedGames) -> PathBuf {
+    utils::path::generate_store_path(app_handle, &format!("save_backups/{}", game_id))
+}
+
+// Backups are grouped by the campaign and faction of the decoded save header.
+// Renamed saves and autosaves of the same campaign then share a retention limit.
+// A save whose header can't be read is grouped by its own name, rather than
+// guessing which campaign it belongs to.
+fn campaign_key(header: &SaveHeader, path: &Path) -> String {
+    let key = match (&header.campaign, &header.faction) {
+        (Some(campaign), Some(faction)) => format!("{}-{}", campaign, faction),
+        _ => path
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string(),
+    };
+
+    key.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ' ') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+// `(path, save name)` of the backups in a campaign folder, oldest first
+fn backup_files(dir: &Path) -> io::Result<Vec<(PathBuf, String)>> {
+    let mut files: Vec<(PathBuf, String)> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file())
+        .filter_map(|path| {
+            let file_name = path.file_name()?.to_str()?;
+            let (_, save_name) = file_name.split_once(BACKUP_NAME_SEPARATOR)?;
+            let save_name = save_name.to_owned();
+            Some((path, save_name))
+        })
+        .filter(|(_, save_name)| !save_name.ends_with(".partial"))
+        .collect();
+
+    files.sort();
+
+    Ok(files)
+}
+
+fn prune(dir: &Path, max_per_campaign: u32) -> io::Result<()> {
+    if max_per_campaign == 0 {
+        return Ok(());
+    }
+
+    let files = backup_files(dir)?;
+    let excess = files.len().saturating_sub(max_per_campaign as usize);
+
+    for (path, _) in files.into_iter().take(excess) {
+        log::debug!("Removing old save backup {}", path.display());
+        std::fs::remove_file(path)?;
+    }
+
+    Ok(())
+}
+
+fn wait_until_settled(path: &Path) -> bool {
+    let snapshot = |path: &Path| {
+        std::fs::metadata(path)
+            .ok()
+            .map(|m| (m.len(), m.modified().ok()))
+    };
+
+    let mut last = snapshot(path);
+
+    for _ in 0..MAX_SETTLE_CHECKS {
+        std::thread::sleep(SETTLE_DELAY);
+
+        let current = snapshot(path);
+        if current.is_none() {
+            return false;
+        }
+        if current == last {
+            return true;
+        }
+        last = current;
+    }
+
+    log::warn!("Save {} kept changing, skipping backup", path.display());
+    false
+}
+
+fn files_equal(a: &Path, b: &Path) -> io::Result<bool> {
+    if std::fs::metadata(a)?.len() != std::fs::metadata(b)?.len() {
+        return Ok(false);
+    }
+
+    let mut a = File::open(a)?;
+    let mut b = File::open(b)?;
+    let mut buf_a = vec![0; 64 * 1024];
+    let mut buf_b = vec![0; 64 * 1024];
+
+    loop {
+        let read = a.read(&mut buf_a)?;
+        if read == 0 {
+            return Ok(true);
+        }
+
+        b.read_exact(&mut buf_b[..read])?;
+        if buf_a[..read] != buf_b[..read] {
+            return Ok(false);
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use std::{fs::File, io::BufReader, path::Path};
+
+use rpfm_lib::files::{
+    Decodeable,
+    esf::{ESF, NodeType, RecordNode},
+};
+
+use crate::mods::pack::ModPack;
+
+// The record holding what the load screen shows about a save.
+const HEADER_RECORD: &str = "SAVE_GAME_HEADER";
+
+// ESF values carry no names, only records do, so the header fields are known by
+// their slot in the record's first block.
+const FACTION_FIELD: usize = 0;
+const CAMPAIGN_FIELD: usize = 1;
+const TURN_FIELD: usize = 2;
+
+/// What the header record of a save says about it.
+///
+/// Saves are ESF files, decoded with rpfm_lib. The header record holds the
+/// player's faction key, the campaign key and the turn in its first slots; when
+/// a slot doesn't hold the expected kind of value the layout is unknown and the
+/// three are left empty. The packs the save was made with are the `.pack`
+/// entries in the header.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveHeader {
+    pub campaign: Option<String>,
+    pub faction: Option<String>,
+    pub turn: Option<u32>,
+    pub packs: Vec<String>, // pack names (without extension) in the order the save lists them
+}
+
+impl SaveHeader {
+    pub fn read(path: &Path) -> std::io::Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let esf = ESF::decode(&mut reader, &None).map_err(std::io::Error::other)?;
+
+        let Some(record) = find_record(esf.root_node(), HEADER_RECORD) else {
+            log::debug!("Save {} has no {} record", path.display(), HEADER_RECORD);
+            return Ok(Self::default());
+        };
+
+        let (faction, campaign, turn) = match read_fields(record) {
+            Some((faction, campaign, turn)) => (Some(faction), Some(campaign), Some(turn)),
+            None => {
+                log::debug!(
+                    "Save {} has an unknown {} layout (version {})",
+                    path.display(),
+                    HEADER_RECORD,
+                    record.version()
+                );
+                (None, None, None)
+            }
+        };
+
+        let mut packs = vec![];
+        collect_packs(record, &mut packs);
+
+        let header = Self {
+            campaign,
+            faction,
+            turn,
+            packs,
+        };
+
+        log::debug!(
+            "Save header {}: campaign={:?}, faction={:?}, turn={:?}, {} pack(s)",
+            path.display(),
+            header.campaign,
+            header.faction,
+            header.turn,
+            header.packs.len()
+        );
+
+        Ok(header)
+    }
+}
+
+fn find_record<'a>(node: &'a NodeType, name: &str) -> Option<&'a RecordNode> {
+    let NodeType::Record(record) = node else {
+        return None;
+    };
+
+    if record.name() == name {
+        return Some(record);
+    }
+
+    record
+        .children()
+        .iter()
+        .flatten()
+        .find_map(|child| find_record(child, name))
+}
+
+fn read_fields(record: &RecordNode) -> Option<(String, String, u32)> {
+    let values = record.children().first()?;
+
+    let faction = node_key(values.get(FACTION_FIELD)?)?;
+    let campaign = node_key(values.get(CAMPAIGN_FIELD)?)?;
+    let turn = match values.get(TURN_FIELD)? {
+        NodeType::U32(node) => *node.value(),
+        NodeType::I32(node) => u32::try_from(*node.value()).ok()?,
+        _ => return None,
+    };
+
+    Some((faction, campaign, turn))
+}
+
+// Database keys are plain identifiers, anything else means the slot holds
+// something other than a key.
+fn node_key(node: &NodeType) -> Option<String> {
+    let value = match node {
+        NodeType::Ascii(value) | NodeType::Utf16(value) => value,
+        _ => return None,
+    };
+
+    let is_key = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+
+    is_key.then(|| value.clone())
+}
+
+fn collect_packs(record: &RecordNode, packs: &mut Vec<String>) {
+    for node in record.children().iter().flatten() {
+        let values: Vec<&String> = match node {
+            NodeType::Record(child) => {
+                collect_packs(child, packs);
+                continue;
+            }
+            NodeType::AsciiArray(values) | NodeType::Utf16Array(values) => values.iter().collect(),
+            NodeType::Ascii(value) | NodeType::Utf16(value) => vec![value],
+            _ => continue,
+        };
+
+        for value in values {
+            if !value.to_lowercase().ends_with(".pack") {
+                continue;
+            }
+
+            let file_name = value.rsplit(['/', '\\']).next().unwrap_or(value);
+            let name = ModPack::dependency_name(file_name).to_owned();
+            if !packs.contains(&name) {
+                packs.push(name);
+            }
+        }
+    }
+}
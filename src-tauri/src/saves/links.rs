@@ -0,0 +1,115 @@
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Duration,
+};
+
+use tauri::Manager;
+
+use crate::{
+    dto::mods::ModResponseDto,
+    state::State,
+    stores::games::{GameStore, SaveLink},
+};
+
+// A save fires several watcher events while it's written, they are handled
+// once after this delay.
+const LINK_DELAY: Duration = Duration::from_secs(2);
+
+// Saves waiting for the delay, so repeated events don't queue more links.
+static PENDING_LINKS: Mutex<BTreeSet<PathBuf>> = Mutex::new(BTreeSet::new());
+
+/// Identifies the exact list of mods a profile loads: the enabled mods, in load
+/// order. Two profiles writing the same mod list get the same hash.
+pub fn mod_list_hash(mods: &[ModResponseDto]) -> String {
+    let mut enabled: Vec<&ModResponseDto> = mods.iter().filter(|m| m.enabled).collect();
+    enabled.sort_by_key(|m| m.order);
+
+    // FNV-1a: stable across builds and platforms, unlike the std hasher
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for m in enabled {
+        for byte in m.name.bytes().chain(std::iter::once(b'\n')) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    format!("{:016x}", hash)
+}
+
+pub fn is_save_file(path: &Path) -> bool {
+    path.extension().and_then(|ext| ext.to_str()) == Some("save")
+}
+
+/// Called by the folder watcher when a save is written. While a game launched
+/// by us is running, the save gets linked to the profile it was started with.
+pub(crate) fn link_written_save(app_handle: &tauri::AppHandle, path: &Path) {
+    if let Ok(mut pending) = PENDING_LINKS.lock()
+        && !pending.insert(path.to_path_buf())
+    {
+        return;
+    }
+
+    let app_handle = app_handle.clone();
+    let path = path.to_path_buf();
+
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(LINK_DELAY).await;
+
+        if let Ok(mut pending) = PENDING_LINKS.lock() {
+            pending.remove(&path);
+        }
+
+        let session = {
+            let state = app_handle.state::<tauri::async_runtime::Mutex<State>>();
+            let state = state.lock().await;
+            state.active_session.clone()
+        };
+
+        let Some(session) = session else {
+            return;
+        };
+
+        if session.saves_path.as_deref() != path.parent() {
+            return;
+        }
+
+        let Some(save_name) = path.file_name().map(|n| n.to_string_lossy().to_string()) else {
+            return;
+        };
+
+        let result = GameStore::get(&app_handle, session.game_id, |game| {
+            // a save written again keeps its link, unless the launch changed
+            if let Some(existing) = game.save_links.get(&save_name)
+                && existing.profile_id == session.profile_id
+                && existing.mods_hash == session.mods_hash
+            {
+                return Ok(false);
+            }
+
+            game.save_links.insert(
+                save_name.clone(),
+                SaveLink {
+                    profile_id: session.profile_id,
+                    mods_hash: session.mods_hash.clone(),
+                    linked_at: chrono::Utc::now().to_rfc3339(),
+                },
+            );
+
+            Ok(true)
+        })
+        .await;
+
+        match result {
+            Ok(true) => log::info!(
+                "Linked save '{}' to profile {} (mods {})",
+                save_name,
+                session.profile_id,
+                session.mods_hash
+            ),
+            Ok(false) => {}
+            Err(e) => log::warn!("Failed to link save '{}': {:?}", save_name, e),
+        }
+    });
+}
@@ -0,0 +1,3 @@
+pub mod backups;
+pub mod header;
+pub mod links;
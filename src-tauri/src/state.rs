@@ -1,10 +1,28 @@
-use crate::{events::AppEvent, launchers::GameManager, utils::folder_watcher};
+use crate::{
+    events::AppEvent,
+    launchers::{GameManager, monitor::GameProcess},
+    saves::{backups, links},
+    supported_games::SupportedGames,
+    utils::folder_watcher,
+};
 use notify::Event;
-use tauri::{AppHandle, Emitter, async_runtime::Mutex};
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager, async_runtime::Mutex};
 
 pub struct State {
     pub game_runner: Option<Box<dyn GameManager>>,
     pub folder_watcher: folder_watcher::FolderWatcher,
+    pub active_session: Option<ActiveSession>, // set while a game launched by us is running
+    pub game_process: Option<GameProcess>,
+    pub watched_saves: Option<(SupportedGames, PathBuf)>, // the saves folder of the game being watched
+}
+
+#[derive(Debug, Clone)]
+pub struct ActiveSession {
+    pub game_id: SupportedGames,
+    pub profile_id: uuid::Uuid,
+    pub mods_hash: String,
+    pub saves_path: Option<PathBuf>,
 }
 
 pub type AppState<'a> = tauri::State<'a, Mutex<State>>;
@@ -19,18 +37,30 @@ impl State {
         Self {
             folder_watcher: folder_watcher::FolderWatcher::new(watcher),
             game_runner: None,
+            active_session: None,
+            game_process: None,
+            watched_saves: None,
         }
     }
 }
 
 fn watcher_sentry(event: Result<Event, notify::Error>, app_handle: &AppHandle) {
     match event {
-        Ok(e) => match e.kind {
-            notify::EventKind::Create(_) | notify::EventKind::Remove(_) => {
-                folders_governor(e, app_handle);
+        Ok(e) => {
+            if matches!(
+                e.kind,
+                notify::EventKind::Create(_) | notify::EventKind::Modify(_)
+            ) {
+                saves_governor(&e, app_handle);
+            }
+
+            match e.kind {
+                notify::EventKind::Create(_) | notify::EventKind::Remove(_) => {
+                    folders_governor(e, app_handle);
+                }
+                _ => {}
             }
-            _ => {}
-        },
+        }
         Err(e) => {
             log::error!("Error watching folder: {:?}", e);
         }
@@ -47,3 +77,39 @@ fn folders_governor(event: Event, app_handle: &AppHandle) {
         .emit(AppEvent::RefreshGame.into(), ())
         .expect("It wasn't possible to emit the event");
 }
+
+// The state is read on a task: the watcher's thread must not wait for it, the
+// state is locked while the watched folders change.
+fn saves_governor(event: &Event, app_handle: &AppHandle) {
+    let paths: Vec<PathBuf> = event
+        .paths
+        .iter()
+        .filter(|p| links::is_save_file(p))
+        .cloned()
+        .collect();
+    if paths.is_empty() {
+        return;
+    }
+
+    let app_handle = app_handle.clone();
+
+    tauri::async_runtime::spawn(async move {
+        let watched_saves = {
+            let state = app_handle.state::<Mutex<State>>();
+            let state = state.lock().await;
+            state.watched_saves.clone()
+        };
+
+        let Some((game_id, saves_path)) = watched_saves else {
+            return;
+        };
+
+        for path in paths
+            .iter()
+            .filter(|p| p.parent() == Some(saves_path.as_path()))
+        {
+            links::link_written_save(&app_handle, path);
+            backups::backup_written_save(&app_handle, game_id, path);
+        }
+    });
+}
@@ -1,7 +1,8 @@
 use crate::{
     defaults::games::DefaultGameInfo,
     dto::{groups::GroupRequestDto, mods::ModRequestDto, profiles::ProfileRequestDto},
-    mods::pack,
+    launchers::{hooks::LaunchHook, options::LaunchOptions, runners::RunnerSelection},
+    mods::{masterlist::Masterlist, pack, rules::LoadOrderRule},
     resolve_existing_path,
     supported_games::SupportedGames,
     utils::{self, ErrorCode, path::retrieve_saves_absolute_path, steam::SteamConfig},
@@ -10,6 +11,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::{collections::HashMap, path::PathBuf, sync::Arc};
 use tauri::Wry;
+use tauri_plugin_store::StoreExt;
 
 pub(crate) trait Store<T> {
     fn find_by_id(
@@ -63,6 +65,24 @@ pub struct GameStore {
     pub groups: Vec<Group>,
     pub default_profile: Option<uuid::Uuid>,
     pub mod_custom_names: HashMap<String, String>, // pack name -> custom name, shared across all profiles of this game
+    #[serde(default)]
+    pub profile_snapshots: HashMap<uuid::Uuid, Vec<ProfileSnapshot>>, // profile id -> previous states, oldest first
+    #[serde(default)]
+    pub save_links: HashMap<String, SaveLink>, // save file name -> profile that last wrote it
+    #[serde(default)]
+    pub play_sessions: Vec<PlaySession>, // oldest first, at most `MAX_PLAY_SESSIONS`
+    #[serde(default)]
+    pub play_totals: HashMap<uuid::Uuid, PlayTotals>, // profile id -> totals over every session, dropped ones included
+    #[serde(default)]
+    pub runner: RunnerSelection,
+    #[serde(default)]
+    pub profile_runners: HashMap<uuid::Uuid, RunnerSelection>, // profile id -> runner used instead of the game's
+    #[serde(default)]
+    pub hooks: Vec<LaunchHook>, // run in order within each stage
+    #[serde(default)]
+    pub load_order_rules: Vec<LoadOrderRule>,
+    #[serde(default)]
+    pub masterlist: Option<Masterlist>, // applied on top of the user's rules
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -81,9 +101,11 @@ pub struct Profile {
     pub mods: Vec<ProfileModInfo>,
     pub manual_mode: bool,
     pub groups: Vec<uuid::Uuid>,
+    #[serde(default)]
+    pub launch_options: LaunchOptions, // only set through `set_profile_launch_options`
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ProfileModInfo {
     pub name: String,
@@ -92,6 +114,82 @@ pub struct ProfileModInfo {
     pub order: u32,                      // TODO: this should be an option
 }
 
+/// State of a profile before one of its changes, kept so it can be restored.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileSnapshot {
+    pub id: uuid::Uuid,
+    pub created_at: String,
+    pub mods: Vec<ProfileModInfo>,
+    pub manual_mode: bool,
+    pub groups: Vec<uuid::Uuid>,
+}
+
+/// The profile and mod list that were active when a save was last written.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveLink {
+    pub profile_id: uuid::Uuid,
+    pub mods_hash: String, // see `saves::links::mod_list_hash`
+    pub linked_at: String,
+}
+
+/// A run of the game started by us, recorded when it ends.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaySession {
+    pub id: uuid::Uuid,
+    pub profile_id: uuid::Uuid,
+    pub save_name: Option<String>, // the save the game was launched with
+    pub started_at: String,
+    pub ended_at: String,
+    pub duration_secs: u64,
+    pub outcome: SessionOutcome,
+}
+
+/// Running totals of a profile's sessions, kept apart from `play_sessions` so they
+/// survive the sessions being capped.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayTotals {
+    pub sessions: usize,
+    pub crashes: usize,
+    pub total_secs: u64,
+    pub last_played: Option<String>,
+}
+
+impl PlayTotals {
+    pub fn add(&mut self, session: &PlaySession) {
+        self.sessions += 1;
+        self.total_secs += session.duration_secs;
+        if session.outcome == SessionOutcome::Crashed {
+            self.crashes += 1;
+        }
+        if self.last_played.as_ref() < Some(&session.ended_at) {
+            self.last_played = Some(session.ended_at.clone());
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SessionOutcome {
+    Exited,
+    Crashed,
+    Stopped,    // through `stop_game`
+    NotStarted, // the game process never showed up
+}
+
+// Held for the whole read-modify-write of `GameStore::get`, so concurrent
+// changes (commands, the save watcher) don't overwrite each other.
+static WRITE_LOCK: tauri::async_runtime::Mutex<()> = tauri::async_runtime::Mutex::const_new(());
+
+// older snapshots are dropped past this count, per profile
+const MAX_PROFILE_SNAPSHOTS: usize = 20;
+
+// older sessions are dropped past this count, per game
+pub(crate) const MAX_PLAY_SESSIONS: usize = 500;
+
 impl From<ModRequestDto> for ProfileModInfo {
     fn from(dto: ModRequestDto) -> Self {
         Self {
@@ -120,6 +218,7 @@ impl From<ProfileRequestDto> for Profile {
                 .collect(),
             manual_mode: dto.manual_mode.unwrap_or(false),
             groups: dto.groups,
+            launch_options: LaunchOptions::default(),
         }
     }
 }
@@ -181,6 +280,14 @@ impl GameStore {
         app_handle: &tauri::AppHandle,
         game_id: SupportedGames,
     ) -> Result<Arc<tauri_plugin_store::Store<Wry>>, ErrorCode> {
+        let game_conf_path =
+            utils::path::generate_store_path(app_handle, &format!("{}.json", game_id));
+
+        // the defaults scan the packs, only build them when the store isn't loaded yet
+        if let Some(store) = app_handle.get_store(&game_conf_path) {
+            return Ok(store);
+        }
+
         let steam_config = SteamConfig::from_app_handle(app_handle)?;
         let default_game = Self::new_game(game_id, &steam_config)
             .unwrap_or_else(|| Self {
@@ -192,12 +299,19 @@ impl GameStore {
                 groups: vec![],
                 default_profile: None,
                 mod_custom_names: HashMap::new(),
+                profile_snapshots: HashMap::new(),
+                save_links: HashMap::new(),
+                play_sessions: vec![],
+                play_totals: HashMap::new(),
+                runner: RunnerSelection::default(),
+                profile_runners: HashMap::new(),
+                hooks: vec![],
+                load_order_rules: vec![],
+                masterlist: None,
             })
             .to_hashmap()
             .or(Err(ErrorCode::InternalError))?;
 
-        let game_conf_path =
-            utils::path::generate_store_path(app_handle, &format!("{}.json", game_id));
         log::debug!(
             "Game store path for {}: {}",
             game_id,
@@ -241,6 +355,15 @@ impl GameStore {
             profiles: vec![default_profile],
             groups: vec![],
             mod_custom_names: HashMap::new(),
+            profile_snapshots: HashMap::new(),
+            save_links: HashMap::new(),
+            play_sessions: vec![],
+            play_totals: HashMap::new(),
+            runner: RunnerSelection::default(),
+            profile_runners: HashMap::new(),
+            hooks: vec![],
+            load_order_rules: vec![],
+            masterlist: None,
         })
     }
 
@@ -290,13 +413,27 @@ impl GameStore {
     where
         F: FnOnce(&mut GameStore) -> Result<R, ErrorCode>,
     {
+        let _guard = WRITE_LOCK.lock().await;
+
         let store = GameStore::get_store(app_handle, game_id)?;
         let mut game = GameStore::from_entries(store.entries())?;
 
+        let previous_profiles = game.profiles.clone();
+
         let result = f(&mut game)?;
 
+        game.snapshot_changed_profiles(previous_profiles);
+
+        let mut changed = false;
         for (k, v) in game.to_hashmap().or(Err(ErrorCode::InternalError))? {
-            store.set(k, v);
+            if store.get(&k).as_ref() != Some(&v) {
+                store.set(k, v);
+                changed = true;
+            }
+        }
+
+        if !changed {
+            return Ok(result);
         }
 
         store.save().map_err(|e| {
@@ -306,4 +443,48 @@ impl GameStore {
 
         Ok(result)
     }
+
+    // Every mutation goes through `get`, so comparing the profiles before and
+    // after it is enough to record a snapshot for each change.
+    fn snapshot_changed_profiles(&mut self, previous_profiles: Vec<Profile>) {
+        let created_at = chrono::Utc::now().to_rfc3339();
+
+        for previous in previous_profiles {
+            let Some(current) = self.profiles.iter().find(|p| p.id == previous.id) else {
+                continue;
+            };
+
+            if current.mods == previous.mods
+                && current.manual_mode == previous.manual_mode
+                && current.groups == previous.groups
+            {
+                continue;
+            }
+
+            let snapshots = self.profile_snapshots.entry(previous.id).or_default();
+            snapshots.push(ProfileSnapshot {
+                id: uuid::Uuid::new_v4(),
+                created_at: created_at.clone(),
+                mods: previous.mods,
+                manual_mode: previous.manual_mode,
+                groups: previous.groups,
+            });
+
+            if snapshots.len() > MAX_PROFILE_SNAPSHOTS {
+                let excess = snapshots.len() - MAX_PROFILE_SNAPSHOTS;
+                snapshots.drain(..excess);
+            }
+
+            log::debug!(
+                "Snapshot recorded for profile {} ({} kept)",
+                previous.id,
+                snapshots.len()
+            );
+        }
+
+        // history of deleted profiles
+        let profiles = &self.profiles;
+        self.profile_snapshots
+            .retain(|id, _| profiles.iter().any(|p| &p.id == id));
+    }
 }
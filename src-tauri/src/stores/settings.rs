@@ -1,5 +1,8 @@
 use crate::{
     defaults::{self},
+    launchers::runners::UmuSource,
+    mods::validation::DependencyPolicy,
+    saves::backups::SaveBackupSettings,
     supported_games::SupportedGames,
     utils::{self, ErrorCode},
 };
@@ -14,6 +17,9 @@ pub enum SettingsKey {
     SteamPath,
     SteamLibraryPath,
     InvertModNames,
+    DependencyPolicy,
+    SaveBackups,
+    UmuSource,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +28,9 @@ pub struct SettingsStore {
     pub steam_path: Option<PathBuf>,
     pub steam_library_path: Option<PathBuf>,
     pub invert_mod_names: bool,
+    pub dependency_policy: DependencyPolicy,
+    pub save_backups: SaveBackupSettings,
+    pub umu_source: UmuSource,
 }
 
 impl From<SettingsKey> for String {
@@ -31,6 +40,9 @@ impl From<SettingsKey> for String {
             SettingsKey::SteamPath => "steam_path".to_string(),
             SettingsKey::SteamLibraryPath => "steam_library_path".to_string(),
             SettingsKey::InvertModNames => "invert_mod_names".to_string(),
+            SettingsKey::DependencyPolicy => "dependency_policy".to_string(),
+            SettingsKey::SaveBackups => "save_backups".to_string(),
+            SettingsKey::UmuSource => "umu_source".to_string(),
         }
     }
 }
@@ -42,6 +54,9 @@ impl Default for SettingsStore {
             steam_path: None,
             steam_library_path: None,
             invert_mod_names: false,
+            dependency_policy: DependencyPolicy::default(),
+            save_backups: SaveBackupSettings::default(),
+            umu_source: UmuSource::default(),
         }
     }
 }
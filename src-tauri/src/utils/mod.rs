@@ -9,6 +9,7 @@ pub mod steam_client;
 
 #[derive(Debug, serde::Serialize)]
 pub enum ErrorCode {
+    BadRequest = 400,
     NotFound = 404,
     InternalError = 500,
     Conflict = 409,
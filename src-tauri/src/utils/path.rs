@@ -30,8 +30,9 @@ pub fn retrieve_saves_absolute_path(
 
     let path = match std::env::consts::OS {
         "windows" => {
-            log::info!("Saves path (windows data dir): {}", data_dir.display());
-            Some(data_dir)
+            let path = join_path!(&data_dir, relative_path);
+            log::info!("Saves path (windows): {}", path.display());
+            Some(path)
         }
         _ => {
             if let Some(steam_path) = steam_config.get_steam_path() {